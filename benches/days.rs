@@ -0,0 +1,74 @@
+//! Criterion benchmarks of the parse, Part One and Part Two stages of every day, on stable Rust.
+//!
+//! These reuse the stages which 'aoc bench' times, so each day's hot path (e.g., the Day 16 beam graph) is covered as
+//! soon as the day registers its 'bench' function. The parse benchmark only parses, and both parts run on an input
+//! which was parsed once up front.
+use aoc23::bench::Stage;
+use aoc23::days::benches;
+use aoc23::days::day14_parabolic::Platform;
+use aoc23::days::day16_lava::BestEnergySearch;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::Path;
+
+fn bench_days(c: &mut Criterion) {
+    for bench in benches() {
+        let in_txt = aoc23::input::read_input(Path::new(bench.input))
+            .unwrap_or_else(|e| panic!("Read input from {:?}: {:?}", bench.input, e));
+        let (_, parsed) = bench
+            .time_parse(&in_txt)
+            .unwrap_or_else(|e| panic!("Day {}: {:?}", bench.day, e));
+
+        let mut group = c.benchmark_group(format!("day{:02}", bench.day));
+        // Dropping the parsed input is not part of parsing it.
+        group.bench_function("parse", |b| b.iter_with_large_drop(|| (bench.parse)(&in_txt).unwrap()));
+        for stage in [Stage::PartOne, Stage::PartTwo] {
+            // Parts which the day does not solve yet would only measure the call.
+            if !parsed
+                .run_part(stage)
+                .unwrap_or_else(|e| panic!("Day {}: {:?}", bench.day, e))
+            {
+                continue;
+            }
+            group.bench_function(stage.name().replace(' ', "_"), |b| {
+                b.iter(|| parsed.run_part(stage).unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+/// Times single spin cycles on the input and on a dish tiled 4x in each direction, and the whole Part Two search on the
+/// input. The tiled dish takes far too long to settle into a loop for the search to be benchmarked on it.
+fn bench_day14_cycles(c: &mut Criterion) {
+    let in_txt = aoc23::input::read_input(Path::new("input/14.txt"))
+        .unwrap_or_else(|e| panic!("Read input from \"input/14.txt\": {:?}", e));
+    let platform = Platform::parse(&in_txt).unwrap_or_else(|e| panic!("Day 14: {:?}", e));
+    let mut group = c.benchmark_group("day14");
+    for factor in [1, 4] {
+        let mut dish = platform.tiled(factor);
+        group.bench_function(format!("cycle_{}x", factor), |b| b.iter(|| dish.cycle()));
+    }
+    group.bench_function("load_after_cycles", |b| {
+        b.iter(|| platform.load_after_cycles(1_000_000_000))
+    });
+    group.finish();
+}
+
+/// Compares the Part Two search which traces every edge start on its own against the shared beam graph.
+fn bench_day16_best_energy(c: &mut Criterion) {
+    let in_txt = aoc23::input::read_input(Path::new("input/16.txt"))
+        .unwrap_or_else(|e| panic!("Read input from \"input/16.txt\": {:?}", e));
+    let search = BestEnergySearch::parse(&in_txt).unwrap_or_else(|e| panic!("Day 16: {:?}", e));
+    let mut group = c.benchmark_group("day16");
+    group.bench_function("best_energy_traced", |b| b.iter(|| search.traced()));
+    group.bench_function("best_energy_cached", |b| b.iter(|| search.cached()));
+    group.finish();
+}
+
+criterion_group! {
+    name = days;
+    // The slowest stages take a few hundred milliseconds, so keep the default sample count from dragging on.
+    config = Criterion::default().sample_size(20);
+    targets = bench_days, bench_day14_cycles, bench_day16_best_energy
+}
+criterion_main!(days);
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Parsing may reject the input, but must never panic.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(in_txt) = std::str::from_utf8(data) {
+        let _ = aoc23::days::check_input(4, in_txt);
+    }
+});
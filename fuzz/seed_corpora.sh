@@ -0,0 +1,10 @@
+#!/bin/bash
+# Seeds every fuzz target's corpus with the demo inputs of its day, e.g., 'input/07-demo-*.txt' for 'day07_camel_cards'.
+set -euo pipefail
+cd "$(dirname "$0")"
+for target_fpath in fuzz_targets/*.rs; do
+    target="$(basename "${target_fpath}" .rs)"
+    day="${target:3:2}"
+    mkdir -p "corpus/${target}"
+    cp ../input/"${day}"-demo*.txt "corpus/${target}/"
+done
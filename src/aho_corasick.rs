@@ -0,0 +1,199 @@
+// A multi-pattern string matcher.
+
+use std::collections::{HashMap, VecDeque};
+use std::str::CharIndices;
+
+/// An Aho-Corasick automaton which finds all (possibly overlapping) occurrences of a set of patterns in one pass.
+///
+/// Patterns are matched char by char, so any UTF-8 text works, and every pattern carries a value which is handed back
+/// with its matches. If the same pattern is given twice, the first value wins. Empty patterns are ignored.
+pub struct AhoCorasick<V> {
+    /// Trie edges of every node, with the root at index 0.
+    goto: Vec<HashMap<char, usize>>,
+    /// The node for the longest proper suffix of this node's string which is also in the trie.
+    fail: Vec<usize>,
+    /// The pattern spelled by the path to this node, if any.
+    terminal: Vec<Option<usize>>,
+    /// The closest node on the fail chain which is terminal, so reporting all matches skips non-terminal nodes.
+    dict_link: Vec<Option<usize>>,
+    /// Length in bytes and value of every pattern.
+    patterns: Vec<(usize, V)>,
+}
+
+/// One occurrence of a pattern, with 'start' and 'end' as byte offsets into the text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match<'a, V> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a V,
+}
+
+impl<V> AhoCorasick<V> {
+    pub fn new<S: AsRef<str>>(patterns: impl IntoIterator<Item = (S, V)>) -> Self {
+        let mut automaton = AhoCorasick {
+            goto: vec![HashMap::new()],
+            fail: vec![0],
+            terminal: vec![None],
+            dict_link: vec![None],
+            patterns: Vec::new(),
+        };
+        for (pattern, value) in patterns {
+            automaton.add_pattern(pattern.as_ref(), value);
+        }
+        automaton.build_links();
+        automaton
+    }
+
+    fn add_pattern(&mut self, pattern: &str, value: V) {
+        if pattern.is_empty() {
+            return;
+        }
+        let mut node = 0;
+        for ch in pattern.chars() {
+            node = match self.goto[node].get(&ch) {
+                Some(child) => *child,
+                None => {
+                    let child = self.goto.len();
+                    self.goto.push(HashMap::new());
+                    self.fail.push(0);
+                    self.terminal.push(None);
+                    self.dict_link.push(None);
+                    self.goto[node].insert(ch, child);
+                    child
+                }
+            };
+        }
+        if self.terminal[node].is_none() {
+            self.terminal[node] = Some(self.patterns.len());
+            self.patterns.push((pattern.len(), value));
+        }
+    }
+
+    /// Computes fail and dictionary links breadth first, so shallower nodes are always done before deeper ones.
+    fn build_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.goto[0].values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(char, usize)> = self.goto[node].iter().map(|(ch, child)| (*ch, *child)).collect();
+            for (ch, child) in edges {
+                let fail = self.step(self.fail[node], ch);
+                self.fail[child] = fail;
+                self.dict_link[child] = if self.terminal[fail].is_some() {
+                    Some(fail)
+                } else {
+                    self.dict_link[fail]
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// Follows the trie edge for 'ch' from 'node', falling back along fail links as needed.
+    fn step(&self, mut node: usize, ch: char) -> usize {
+        loop {
+            if let Some(child) = self.goto[node].get(&ch) {
+                return *child;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.fail[node];
+        }
+    }
+
+    pub fn n_patterns(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns every occurrence of every pattern in 'text', ordered by end position (and longest first among matches
+    /// which end at the same position).
+    pub fn find_overlapping<'a>(&'a self, text: &'a str) -> OverlappingMatches<'a, V> {
+        OverlappingMatches {
+            automaton: self,
+            chars: text.char_indices(),
+            node: 0,
+            pending: None,
+        }
+    }
+}
+
+pub struct OverlappingMatches<'a, V> {
+    automaton: &'a AhoCorasick<V>,
+    chars: CharIndices<'a>,
+    node: usize,
+    /// The next terminal node to report for the current text position, and the byte offset the match ends at.
+    pending: Option<(usize, usize)>,
+}
+
+impl<'a, V> Iterator for OverlappingMatches<'a, V> {
+    type Item = Match<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let automaton = self.automaton;
+        loop {
+            if let Some((node, end)) = self.pending {
+                self.pending = automaton.dict_link[node].map(|next| (next, end));
+                let (len, value) = &automaton.patterns[automaton.terminal[node].expect("Pending nodes are terminal")];
+                return Some(Match {
+                    start: end - len,
+                    end,
+                    value,
+                });
+            }
+
+            let (pos, ch) = self.chars.next()?;
+            self.node = automaton.step(self.node, ch);
+            let end = pos + ch.len_utf8();
+            self.pending = match automaton.terminal[self.node] {
+                Some(_) => Some((self.node, end)),
+                None => automaton.dict_link[self.node].map(|next| (next, end)),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_aho_corasick_overlapping_matches() {
+        let automaton = AhoCorasick::new([("he", 0), ("she", 1), ("his", 2), ("hers", 3)]);
+        let matches: Vec<(usize, usize, i32)> = automaton
+            .find_overlapping("ushers")
+            .map(|m| (m.start, m.end, *m.value))
+            .collect();
+        assert_eq!(vec![(1, 4, 1), (2, 4, 0), (2, 6, 3)], matches);
+
+        let automaton = AhoCorasick::new([("two", 2), ("one", 1), ("", 0)]);
+        assert_eq!(2, automaton.n_patterns());
+        let values: Vec<i32> = automaton.find_overlapping("xtwonetwo").map(|m| *m.value).collect();
+        assert_eq!(vec![2, 1, 2], values);
+    }
+
+    #[test]
+    fn test_aho_corasick_matches_naive_search_on_utf8() {
+        let patterns = ["șase", "șapte", "nouă", "ă", "fünf", "ün", "aa", "a"];
+        let automaton = AhoCorasick::new(patterns.iter().enumerate().map(|(idx, p)| (p, idx)));
+        let text = "xșaseșaptenouăaaafünfünfzehn🙂șa";
+
+        let mut expected: Vec<(usize, usize, usize)> = Vec::new();
+        for (start, _) in text.char_indices() {
+            for (idx, pattern) in patterns.iter().enumerate() {
+                if text[start..].starts_with(pattern) {
+                    expected.push((start, start + pattern.len(), idx));
+                }
+            }
+        }
+        expected.sort();
+
+        let mut found: Vec<(usize, usize, usize)> = automaton
+            .find_overlapping(text)
+            .map(|m| (m.start, m.end, *m.value))
+            .collect();
+        assert!(found
+            .iter()
+            .all(|(start, end, _)| text.is_char_boundary(*start) && text.is_char_boundary(*end)));
+        found.sort();
+        assert_eq!(expected, found);
+    }
+}
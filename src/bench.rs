@@ -0,0 +1,339 @@
+//! Times the parse, Part One and Part Two stages of every day on its full input.
+//!
+//! Each day registers a function which parses its input into 'Parts', which can then run either part on the parsed
+//! input, so every stage is timed on its own. A run of the report repeats every stage 'n_runs' times and keeps the
+//! median and the 95th percentile of each, which can be saved as JSON and later used as the baseline of another run to
+//! catch regressions.
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Stages which are faster than this in both runs are never flagged, since their timings are mostly noise.
+pub const NOISE_FLOOR: Duration = Duration::from_micros(20);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse = 0,
+    PartOne = 1,
+    PartTwo = 2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::PartOne, Stage::PartTwo];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::PartOne => "part one",
+            Stage::PartTwo => "part two",
+        }
+    }
+}
+
+/// A day's input after its parse stage, on which either part can then run on its own, as often as needed.
+pub trait Parsed {
+    /// Runs Part One or Part Two, keeping its answer opaque to the optimizer. Returns false for a part which the day
+    /// does not solve (yet).
+    fn run_part(&self, stage: Stage) -> anyhow::Result<bool>;
+}
+
+/// A parsed input together with the functions which solve each of its parts.
+pub struct Parts<T, A, B> {
+    pub input: T,
+    pub part_one: fn(&T) -> anyhow::Result<A>,
+    pub part_two: Option<fn(&T) -> anyhow::Result<B>>,
+}
+
+impl<T, A, B> Parsed for Parts<T, A, B> {
+    fn run_part(&self, stage: Stage) -> anyhow::Result<bool> {
+        let answer = match stage {
+            Stage::Parse => anyhow::bail!("Parsing is not a part"),
+            Stage::PartOne => (self.part_one)(&self.input).map(drop),
+            Stage::PartTwo => match self.part_two {
+                Some(part_two) => part_two(&self.input).map(drop),
+                None => return Ok(false),
+            },
+        };
+        std::hint::black_box(answer)?;
+        Ok(true)
+    }
+}
+
+/// How a day parses a full input. Every part then runs on the parsed input, so that each stage is timed on its own.
+pub struct DayBench {
+    pub day: u32,
+    pub input: &'static str,
+    pub parse: fn(&str) -> anyhow::Result<Box<dyn Parsed + '_>>,
+}
+
+impl DayBench {
+    /// Times one parse of 'in_txt', without the time it takes to drop the parsed input again.
+    pub fn time_parse<'a>(&self, in_txt: &'a str) -> anyhow::Result<(Duration, Box<dyn Parsed + 'a>)> {
+        let start = Instant::now();
+        let parsed = std::hint::black_box((self.parse)(in_txt))?;
+        Ok((start.elapsed(), parsed))
+    }
+}
+
+/// Times one run of Part One or Part Two, or returns 'None' for a part which the day does not solve.
+pub fn time_part(parsed: &dyn Parsed, stage: Stage) -> anyhow::Result<Option<Duration>> {
+    let start = Instant::now();
+    let solved = parsed.run_part(stage)?;
+    Ok(solved.then(|| start.elapsed()))
+}
+
+/// Summary statistics of one stage over all runs, in nanoseconds so that the JSON stays exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageStats {
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl StageStats {
+    /// Uses the nearest-rank percentiles of 'samples'.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Cannot summarize zero samples");
+        let mut sorted: Vec<u64> = samples.iter().map(|sample| sample.as_nanos() as u64).collect();
+        sorted.sort_unstable();
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+        StageStats {
+            median_ns: percentile(50),
+            p95_ns: percentile(95),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: u32,
+    pub input: String,
+    pub parse: StageStats,
+    pub part_one: StageStats,
+    pub part_two: StageStats,
+}
+
+impl DayReport {
+    pub fn stage(&self, stage: Stage) -> &StageStats {
+        match stage {
+            Stage::Parse => &self.parse,
+            Stage::PartOne => &self.part_one,
+            Stage::PartTwo => &self.part_two,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub n_runs: usize,
+    pub days: Vec<DayReport>,
+}
+
+/// A stage whose median got slower than the baseline by more than the threshold.
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u32,
+    pub stage: Stage,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Regression {
+    pub fn slowdown(&self) -> f64 {
+        self.current_ns as f64 / self.baseline_ns.max(1) as f64
+    }
+}
+
+/// Times each stage of 'bench' on its input 'n_runs' times, after one untimed warm-up run. The parts all run on the
+/// same parsed input, and a part which the day does not solve counts as taking no time.
+pub fn bench_day(bench: &DayBench, n_runs: usize) -> anyhow::Result<DayReport> {
+    anyhow::ensure!(n_runs > 0, "Need at least one run");
+    let in_txt = crate::input::read_input(Path::new(bench.input))?;
+
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    bench.time_parse(&in_txt)?;
+    for _ in 0..n_runs {
+        samples[Stage::Parse as usize].push(bench.time_parse(&in_txt)?.0);
+    }
+    let (_, parsed) = bench.time_parse(&in_txt)?;
+    for stage in [Stage::PartOne, Stage::PartTwo] {
+        time_part(parsed.as_ref(), stage)?;
+        for _ in 0..n_runs {
+            let elapsed = time_part(parsed.as_ref(), stage)?;
+            samples[stage as usize].push(elapsed.unwrap_or_default());
+        }
+    }
+    Ok(DayReport {
+        day: bench.day,
+        input: bench.input.to_string(),
+        parse: StageStats::from_samples(&samples[Stage::Parse as usize]),
+        part_one: StageStats::from_samples(&samples[Stage::PartOne as usize]),
+        part_two: StageStats::from_samples(&samples[Stage::PartTwo as usize]),
+    })
+}
+
+/// Formats nanoseconds with a unit which keeps three significant digits or so.
+fn format_ns(ns: u64) -> String {
+    match ns {
+        0..=999 => format!("{} ns", ns),
+        1_000..=999_999 => format!("{:.1} µs", ns as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2} ms", ns as f64 / 1e6),
+        _ => format!("{:.2} s", ns as f64 / 1e9),
+    }
+}
+
+impl Report {
+    /// Renders a table with the median and the p95 of every stage, e.g., for the README.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "| Day | Parse (median / p95) | Part One (median / p95) | Part Two (median / p95) |"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "|----:|---------------------:|------------------------:|------------------------:|"
+        )
+        .unwrap();
+        for day in &self.days {
+            let cells: Vec<String> = Stage::ALL
+                .iter()
+                .map(|stage| {
+                    let stats = day.stage(*stage);
+                    format!("{} / {}", format_ns(stats.median_ns), format_ns(stats.p95_ns))
+                })
+                .collect();
+            writeln!(out, "| {:02} | {} |", day.day, cells.join(" | ")).unwrap();
+        }
+        writeln!(out, "\nMedians and 95th percentiles over {} runs.", self.n_runs).unwrap();
+        out
+    }
+
+    /// Returns the stages whose median is more than 'threshold' (e.g., 0.1 for 10%) slower than in 'baseline'. Days
+    /// missing from either report are skipped, as are stages below the noise floor.
+    pub fn regressions(&self, baseline: &Report, threshold: f64) -> Vec<Regression> {
+        let mut found = Vec::new();
+        for day in &self.days {
+            let Some(baseline_day) = baseline.days.iter().find(|other| other.day == day.day) else {
+                continue;
+            };
+            for stage in Stage::ALL {
+                let current_ns = day.stage(stage).median_ns;
+                let baseline_ns = baseline_day.stage(stage).median_ns;
+                if current_ns.max(baseline_ns) < NOISE_FLOOR.as_nanos() as u64 {
+                    continue;
+                }
+                if current_ns as f64 > baseline_ns as f64 * (1.0 + threshold) {
+                    found.push(Regression {
+                        day: day.day,
+                        stage,
+                        baseline_ns,
+                        current_ns,
+                    });
+                }
+            }
+        }
+        found
+    }
+}
+
+impl std::fmt::Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {:02} {}: {} -> {} ({:.2}x)",
+            self.day,
+            self.stage.name(),
+            format_ns(self.baseline_ns),
+            format_ns(self.current_ns),
+            self.slowdown()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median_ns: u64) -> StageStats {
+        StageStats {
+            median_ns,
+            p95_ns: median_ns * 2,
+        }
+    }
+
+    fn report(stage_ns: [u64; 3]) -> Report {
+        Report {
+            n_runs: 5,
+            days: vec![DayReport {
+                day: 7,
+                input: "input/07.txt".to_string(),
+                parse: stats(stage_ns[0]),
+                part_one: stats(stage_ns[1]),
+                part_two: stats(stage_ns[2]),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_stage_stats_percentiles() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_nanos).collect();
+        assert_eq!(
+            StageStats {
+                median_ns: 10,
+                p95_ns: 19
+            },
+            StageStats::from_samples(&samples)
+        );
+        let single = [Duration::from_micros(3)];
+        assert_eq!(stats(3000).median_ns, StageStats::from_samples(&single).p95_ns);
+    }
+
+    #[test]
+    fn test_regressions_and_markdown() {
+        let baseline = report([1_000, 100_000, 2_000_000]);
+        // Parse is below the noise floor, part one got slightly faster, part two 30% slower.
+        let current = report([5_000, 90_000, 2_600_000]);
+        let regressions = current.regressions(&baseline, 0.1);
+        assert_eq!(1, regressions.len());
+        assert_eq!(Stage::PartTwo, regressions[0].stage);
+        assert_eq!(
+            "Day 07 part two: 2.00 ms -> 2.60 ms (1.30x)",
+            regressions[0].to_string()
+        );
+        assert!(current.regressions(&baseline, 0.5).is_empty());
+
+        let markdown = current.to_markdown();
+        assert!(
+            markdown.contains("| 07 | 5.0 µs / 10.0 µs | 90.0 µs / 180.0 µs | 2.60 ms / 5.20 ms |"),
+            "{}",
+            markdown
+        );
+        let json = serde_json::to_string(&current).unwrap();
+        assert_eq!(current, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_every_day_benches() {
+        let benches = crate::days::benches();
+        assert_eq!(
+            crate::gen::DAYS.collect::<Vec<u32>>(),
+            benches.iter().map(|b| b.day).collect::<Vec<u32>>()
+        );
+        for bench in benches {
+            // Generated inputs keep this quick, and still go through every stage.
+            let in_txt = crate::gen::generate(bench.day, 3, 0).unwrap();
+            let (_, parsed) = bench
+                .time_parse(&in_txt)
+                .unwrap_or_else(|e| panic!("Day {}: {:?}", bench.day, e));
+            for stage in [Stage::PartOne, Stage::PartTwo] {
+                let solved = parsed
+                    .run_part(stage)
+                    .unwrap_or_else(|e| panic!("Day {} {}: {:?}", bench.day, stage.name(), e));
+                // Only Day 10 does not solve Part Two yet.
+                assert_eq!(stage == Stage::PartOne || bench.day != 10, solved, "Day {}", bench.day);
+            }
+        }
+    }
+}
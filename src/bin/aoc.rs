@@ -0,0 +1,142 @@
+use anyhow::Context;
+use aoc23::bench::{bench_day, Report};
+use aoc23::check::check_day;
+use aoc23::days::{benches, cross_checks};
+
+const USAGE: &str = "Usage:
+  aoc check [--day <n>] [--cases <n>] [--seed <n>]
+  aoc gen --day <n> [--seed <n>] [--size <n>] [--out <path>]
+  aoc bench [--day <n>] [--runs <n>] [--json <path>] [--markdown <path>] [--baseline <path>] [--threshold <pct>]";
+
+/// Returns the value following 'flag' in 'args', parsed, or None if the flag is missing.
+fn flag_value<T: std::str::FromStr>(args: &[String], flag: &str) -> anyhow::Result<Option<T>> {
+    match args.iter().position(|arg| arg == flag) {
+        None => Ok(None),
+        Some(idx) => args
+            .get(idx + 1)
+            .and_then(|value| value.parse().ok())
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("Expected a valid value after {}\n{}", flag, USAGE)),
+    }
+}
+
+/// Runs every registered reference and optimized solver on random inputs and reports the first disagreement.
+fn check(args: &[String]) -> anyhow::Result<()> {
+    let day: Option<u32> = flag_value(args, "--day")?;
+    let n_cases: u64 = flag_value(args, "--cases")?.unwrap_or(200);
+    let seed: u64 = flag_value(args, "--seed")?.unwrap_or(0);
+
+    let checks: Vec<_> = cross_checks()
+        .into_iter()
+        .filter(|check| day.is_none_or(|day| check.day == day))
+        .collect();
+    if checks.is_empty() {
+        match day {
+            Some(day) => anyhow::bail!("Day {} registers no reference solver", day),
+            None => anyhow::bail!("No day registers a reference solver"),
+        }
+    }
+
+    // Solvers which crash count as disagreeing, so keep their panic messages out of the report.
+    std::panic::set_hook(Box::new(|_| {}));
+    for check in &checks {
+        match check_day(check, n_cases, seed) {
+            Some(disagreement) => {
+                let _ = std::panic::take_hook();
+                anyhow::bail!("{}", disagreement);
+            }
+            None => {
+                let names: Vec<&str> = check.optimized.iter().map(|solver| solver.name).collect();
+                println!(
+                    "Day {:02}: {} agree(s) with {} on {} cases",
+                    check.day,
+                    names.join(", "),
+                    check.reference.name,
+                    n_cases
+                );
+            }
+        }
+    }
+    let _ = std::panic::take_hook();
+    Ok(())
+}
+
+/// Writes a random input for a day, which is the same for the same seed and size.
+fn gen(args: &[String]) -> anyhow::Result<()> {
+    let day: u32 = flag_value(args, "--day")?.ok_or_else(|| anyhow::anyhow!("Missing --day\n{}", USAGE))?;
+    let seed: u64 = flag_value(args, "--seed")?.unwrap_or(0);
+    let size: usize = flag_value(args, "--size")?.unwrap_or(10);
+    let input = aoc23::gen::generate(day, size, seed)?;
+    match flag_value::<String>(args, "--out")? {
+        Some(out_fpath) => std::fs::write(&out_fpath, input).with_context(|| format!("Write {:?}", out_fpath)),
+        None => {
+            print!("{}", input);
+            Ok(())
+        }
+    }
+}
+
+/// Times every stage of every day on its full input, optionally comparing the medians against a saved report.
+fn bench(args: &[String]) -> anyhow::Result<()> {
+    let day: Option<u32> = flag_value(args, "--day")?;
+    let n_runs: usize = flag_value(args, "--runs")?.unwrap_or(20);
+    let threshold_pct: f64 = flag_value(args, "--threshold")?.unwrap_or(10.0);
+
+    let mut report = Report {
+        n_runs,
+        days: Vec::new(),
+    };
+    for bench in benches().iter().filter(|bench| day.is_none_or(|day| bench.day == day)) {
+        let day_report = bench_day(bench, n_runs).with_context(|| format!("Day {:02}", bench.day))?;
+        eprintln!("Day {:02} done", bench.day);
+        report.days.push(day_report);
+    }
+    if report.days.is_empty() {
+        match day {
+            Some(day) => anyhow::bail!("Day {} has no benchmark", day),
+            None => anyhow::bail!("No day has a benchmark"),
+        }
+    }
+
+    let markdown = report.to_markdown();
+    print!("{}", markdown);
+    if let Some(json_fpath) = flag_value::<String>(args, "--json")? {
+        let json = serde_json::to_string_pretty(&report)?;
+        std::fs::write(&json_fpath, json).with_context(|| format!("Write {:?}", json_fpath))?;
+    }
+    if let Some(markdown_fpath) = flag_value::<String>(args, "--markdown")? {
+        std::fs::write(&markdown_fpath, markdown).with_context(|| format!("Write {:?}", markdown_fpath))?;
+    }
+
+    if let Some(baseline_fpath) = flag_value::<String>(args, "--baseline")? {
+        let baseline_json =
+            std::fs::read_to_string(&baseline_fpath).with_context(|| format!("Read {:?}", baseline_fpath))?;
+        let baseline: Report =
+            serde_json::from_str(&baseline_json).with_context(|| format!("Parse {:?}", baseline_fpath))?;
+        let regressions = report.regressions(&baseline, threshold_pct / 100.0);
+        if !regressions.is_empty() {
+            let lines: Vec<String> = regressions.iter().map(|regression| regression.to_string()).collect();
+            anyhow::bail!(
+                "{} stage(s) regressed by more than {}%:\n{}",
+                regressions.len(),
+                threshold_pct,
+                lines.join("\n")
+            );
+        }
+        println!(
+            "No stage regressed by more than {}% against {}",
+            threshold_pct, baseline_fpath
+        );
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(|command| command.as_str()) {
+        Some("check") => check(&args[1..]),
+        Some("gen") => gen(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        _ => anyhow::bail!(USAGE),
+    }
+}
@@ -0,0 +1,239 @@
+//! Cross-checks optimized solvers against slow but obviously correct reference ones on small random inputs.
+//!
+//! Each day which keeps a brute force around registers it as the reference next to its faster solvers, along with a
+//! generator of inputs small enough for the brute force. When a solver disagrees with the reference, the input is
+//! shrunk line by line, then character by character, for as long as the disagreement persists.
+use crate::gen::GenRng;
+use rand::SeedableRng;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// One way of solving a day, mapping the raw puzzle input to a printable answer.
+pub struct Solver {
+    pub name: &'static str,
+    pub solve: fn(&str) -> String,
+}
+
+/// The solvers a day registers for cross-checking.
+pub struct DayCheck {
+    pub day: u32,
+    pub reference: Solver,
+    pub optimized: Vec<Solver>,
+    /// Draws an input which the reference solves quickly.
+    pub random_input: fn(&mut GenRng) -> String,
+}
+
+/// The answer of a solver, or the message it panicked with.
+pub type Outcome = Result<String, String>;
+
+/// The first input on which an optimized solver disagreed with the reference.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub day: u32,
+    pub solver: &'static str,
+    pub reference: &'static str,
+    /// Seed of the failing case, i.e., 'random_input' with this seed reproduces the original input.
+    pub case_seed: u64,
+    pub original_input: String,
+    pub input: String,
+    pub expected: Outcome,
+    pub actual: Outcome,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {}: '{}' disagrees with '{}' on case seed {}.",
+            self.day, self.solver, self.reference, self.case_seed
+        )?;
+        writeln!(
+            f,
+            "Minimized input ({} of {} lines):",
+            self.input.lines().count(),
+            self.original_input.lines().count()
+        )?;
+        writeln!(f, "{}", self.input.trim_end_matches('\n'))?;
+        writeln!(f, "Expected: {:?}", self.expected)?;
+        write!(f, "Actual:   {:?}", self.actual)
+    }
+}
+
+/// Runs a solver, turning a panic into an error so that a crash also counts as a disagreement.
+pub fn run(solver: &Solver, input: &str) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(|| (solver.solve)(input))).map_err(|payload| {
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|msg| msg.to_string()))
+            .unwrap_or_else(|| "Panicked".to_string())
+    })
+}
+
+/// Runs 'n_cases' random inputs through every solver of 'check', seeding case 'i' with 'seed + i'. Returns the first
+/// disagreement, with its input minimized.
+pub fn check_day(check: &DayCheck, n_cases: u64, seed: u64) -> Option<Disagreement> {
+    for case_idx in 0..n_cases {
+        let case_seed = seed.wrapping_add(case_idx);
+        let input = (check.random_input)(&mut GenRng::seed_from_u64(case_seed));
+        let expected = run(&check.reference, &input);
+        for solver in &check.optimized {
+            let actual = run(solver, &input);
+            if actual == expected {
+                continue;
+            }
+
+            // Only keep shrinking towards the same kind of failure, e.g., not from a wrong answer to a parse crash.
+            let (expected_ok, actual_ok) = (expected.is_ok(), actual.is_ok());
+            let minimized = minimize(&input, |candidate| {
+                let candidate_expected = run(&check.reference, candidate);
+                let candidate_actual = run(solver, candidate);
+                candidate_expected != candidate_actual
+                    && candidate_expected.is_ok() == expected_ok
+                    && candidate_actual.is_ok() == actual_ok
+            });
+            return Some(Disagreement {
+                day: check.day,
+                solver: solver.name,
+                reference: check.reference.name,
+                case_seed,
+                expected: run(&check.reference, &minimized),
+                actual: run(solver, &minimized),
+                original_input: input,
+                input: minimized,
+            });
+        }
+    }
+    None
+}
+
+/// Greedily shrinks 'input' while 'is_failing' holds, first by dropping chunks of lines, then single characters.
+pub fn minimize(input: &str, mut is_failing: impl FnMut(&str) -> bool) -> String {
+    let trailing_newline = input.ends_with('\n');
+    let join = |lines: &[String]| {
+        let mut joined = lines.join("\n");
+        if trailing_newline && !lines.is_empty() {
+            joined.push('\n');
+        }
+        joined
+    };
+
+    let mut lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
+    loop {
+        let n_before: usize = lines.iter().map(|line| line.len() + 1).sum();
+
+        let mut chunk = (lines.len() / 2).max(1);
+        loop {
+            let mut start = 0;
+            while start < lines.len() {
+                let mut candidate = lines.clone();
+                candidate.drain(start..(start + chunk).min(lines.len()));
+                if is_failing(&join(&candidate)) {
+                    lines = candidate;
+                } else {
+                    start += chunk;
+                }
+            }
+            if chunk == 1 {
+                break;
+            }
+            chunk /= 2;
+        }
+
+        for line_idx in 0..lines.len() {
+            let mut char_idx = 0;
+            while char_idx < lines[line_idx].len() {
+                let mut candidate = lines.clone();
+                candidate[line_idx].remove(char_idx);
+                if is_failing(&join(&candidate)) {
+                    lines = candidate;
+                } else {
+                    char_idx += lines[line_idx][char_idx..].chars().next().map_or(1, |ch| ch.len_utf8());
+                }
+            }
+        }
+
+        let n_after: usize = lines.iter().map(|line| line.len() + 1).sum();
+        if n_after == n_before {
+            return join(&lines);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn sum_numbers(input: &str) -> String {
+        input
+            .split_whitespace()
+            .map(|nr| nr.parse::<u64>().unwrap())
+            .sum::<u64>()
+            .to_string()
+    }
+
+    fn sum_small_numbers(input: &str) -> String {
+        input
+            .split_whitespace()
+            .map(|nr| nr.parse::<u64>().unwrap())
+            .filter(|nr| *nr <= 50)
+            .sum::<u64>()
+            .to_string()
+    }
+
+    fn random_numbers(rng: &mut GenRng) -> String {
+        (0..rng.gen_range(1..10))
+            .map(|_| {
+                let row: Vec<String> = (0..rng.gen_range(1..5))
+                    .map(|_| rng.gen_range(0..60).to_string())
+                    .collect();
+                row.join(" ") + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_check_finds_and_minimizes_disagreement() {
+        let check = DayCheck {
+            day: 0,
+            reference: Solver {
+                name: "sum",
+                solve: sum_numbers,
+            },
+            optimized: vec![Solver {
+                name: "sum_small",
+                solve: sum_small_numbers,
+            }],
+            random_input: random_numbers,
+        };
+        let disagreement = check_day(&check, 100, 7).expect("Some number above 50 is drawn");
+        assert_eq!("sum_small", disagreement.solver);
+        // A single number above 50 is all it takes.
+        let number: u64 = disagreement.input.trim().parse().unwrap();
+        assert!(number > 50, "{}", disagreement);
+        assert_eq!(Ok(number.to_string()), disagreement.expected);
+        assert_eq!(Ok("0".to_string()), disagreement.actual);
+
+        // A crash is a disagreement too.
+        let check = DayCheck {
+            optimized: vec![Solver {
+                name: "crash",
+                solve: |input| panic!("Cannot handle {} lines", input.lines().count()),
+            }],
+            ..check
+        };
+        let disagreement = check_day(&check, 1, 0).unwrap();
+        assert_eq!("", disagreement.input);
+        assert_eq!(Err("Cannot handle 0 lines".to_string()), disagreement.actual);
+    }
+
+    #[test]
+    fn test_registered_days_agree() {
+        for check in crate::days::cross_checks() {
+            if let Some(disagreement) = check_day(&check, 30, 0) {
+                panic!("{}", disagreement);
+            }
+        }
+    }
+}
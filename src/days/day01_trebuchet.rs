@@ -0,0 +1,284 @@
+use crate::bench::{DayBench, Parts};
+use crate::input::{open_input, try_for_each_line, Progress, StreamArgs};
+use crate::AhoCorasick;
+use anyhow::Context;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+fn extract_code_digit_only(line: &str) -> i64 {
+    let mut first_digit: i32 = 0;
+    let mut last_digit: i32 = 0;
+    for ch in line.chars() {
+        if ch.is_numeric() {
+            let val = (ch as i32) - ('0' as i32);
+            if first_digit == 0 {
+                first_digit = val;
+            }
+            last_digit = val;
+        }
+    }
+    let code = first_digit * 10 + last_digit;
+    code as i64
+}
+
+/// Number words and their digit values, which count as digits in Part Two.
+struct Vocabulary {
+    words: Vec<(String, i32)>,
+}
+
+const ENGLISH: &[(&str, i32)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+const GERMAN: &[(&str, i32)] = &[
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+const FRENCH: &[(&str, i32)] = &[
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+const ROMANIAN: &[(&str, i32)] = &[
+    ("unu", 1),
+    ("doi", 2),
+    ("trei", 3),
+    ("patru", 4),
+    ("cinci", 5),
+    ("șase", 6),
+    ("șapte", 7),
+    ("opt", 8),
+    ("nouă", 9),
+];
+
+impl Vocabulary {
+    fn from_table(table: &[(&str, i32)]) -> Self {
+        Vocabulary {
+            words: table.iter().map(|(word, digit)| (word.to_string(), *digit)).collect(),
+        }
+    }
+
+    fn english() -> Self {
+        Vocabulary::from_table(ENGLISH)
+    }
+
+    /// Returns a built-in vocabulary by language name, or loads one from a file with a '<word> <digit>' pair per line.
+    fn load(name_or_path: &str) -> anyhow::Result<Self> {
+        match name_or_path {
+            "english" => Ok(Vocabulary::english()),
+            "german" => Ok(Vocabulary::from_table(GERMAN)),
+            "french" => Ok(Vocabulary::from_table(FRENCH)),
+            "romanian" => Ok(Vocabulary::from_table(ROMANIAN)),
+            fpath => {
+                let spec =
+                    std::fs::read_to_string(fpath).with_context(|| format!("Read vocabulary from {:?}", fpath))?;
+                Vocabulary::parse(&spec)
+            }
+        }
+    }
+
+    fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut words = Vec::new();
+        for line in spec.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let [word, digit] = parts[..] else {
+                anyhow::bail!("Expected '<word> <digit>', got {:?}", line);
+            };
+            let digit: i32 = digit.parse().with_context(|| format!("Parse digit in {:?}", line))?;
+            anyhow::ensure!((1..=9).contains(&digit), "Digit out of range in {:?}", line);
+            words.push((word.to_string(), digit));
+        }
+        Ok(Vocabulary { words })
+    }
+}
+
+/// Finds the first and last digit of a line, where digits may be spelled out as words from a vocabulary.
+///
+/// All words (and the digits themselves) go into a single Aho-Corasick automaton, so each line is scanned once, and
+/// overlapping words like "twone" are all found.
+struct DigitFinder {
+    automaton: AhoCorasick<i32>,
+}
+
+impl DigitFinder {
+    fn new(vocabulary: &Vocabulary) -> Self {
+        let digits = (1..=9).map(|digit| (digit.to_string(), digit));
+        DigitFinder {
+            automaton: AhoCorasick::new(vocabulary.words.iter().cloned().chain(digits)),
+        }
+    }
+
+    /// Returns the first and last digit by position in 'line', if there are any.
+    fn first_and_last(&self, line: &str) -> Option<(i32, i32)> {
+        let mut first: Option<(usize, i32)> = None;
+        let mut last: Option<(usize, i32)> = None;
+        for m in self.automaton.find_overlapping(line) {
+            if first.is_none_or(|(start, _)| m.start < start) {
+                first = Some((m.start, *m.value));
+            }
+            if last.is_none_or(|(start, _)| m.start > start) {
+                last = Some((m.start, *m.value));
+            }
+        }
+        Some((first?.1, last?.1))
+    }
+}
+
+fn extract_code_smart(finder: &DigitFinder, line: &str) -> anyhow::Result<i64> {
+    let (first_digit, last_digit) = finder
+        .first_and_last(line)
+        .with_context(|| format!("No digit in line {:?}", line))?;
+    let code = first_digit * 10 + last_digit;
+    Ok(code as i64)
+}
+
+/// Sums the calibration values of both parts line by line, reading Part Two number words from 'vocabulary'.
+fn solve_stream(
+    reader: impl BufRead,
+    vocabulary: &Vocabulary,
+    progress_every: Option<usize>,
+) -> anyhow::Result<(i64, i64)> {
+    let finder = DigitFinder::new(vocabulary);
+    let mut progress = Progress::new(progress_every);
+    let (mut part_one_code, mut part_two_code) = (0i64, 0i64);
+    try_for_each_line(reader, |line| {
+        part_one_code += extract_code_digit_only(line);
+        part_two_code += extract_code_smart(&finder, line)?;
+        progress.tick(|| (part_one_code, part_two_code));
+        Ok(())
+    })?;
+    Ok((part_one_code, part_two_code))
+}
+
+/// Parses an input without solving it. Every line needs a digit, spelled out or not.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    let finder = DigitFinder::new(&Vocabulary::english());
+    try_for_each_line(in_txt.as_bytes(), |line| extract_code_smart(&finder, line).map(drop))
+}
+
+/// Times splitting the document into lines, then each part's calibration sum.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 1,
+        input: "input/01.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: in_txt.lines().collect::<Vec<&str>>(),
+                part_one: |lines| Ok(lines.iter().map(|line| extract_code_digit_only(line)).sum::<i64>()),
+                part_two: Some(|lines| {
+                    let finder = DigitFinder::new(&Vocabulary::english());
+                    lines
+                        .iter()
+                        .map(|line| extract_code_smart(&finder, line))
+                        .sum::<anyhow::Result<i64>>()
+                }),
+            }))
+        },
+    }
+}
+
+fn day_01_trebuchet(fpath: &Path) -> anyhow::Result<(i64, i64)> {
+    open_input(fpath).and_then(|reader| solve_stream(reader, &Vocabulary::english(), None))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--vocabulary <english|german|french|romanian|path> <input>' to calibrate a document in another language,
+    // or '--stream <path|-> [--progress <n_lines>]' to solve a (possibly huge) input line by line.
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() == 4 && args[1] == "--vocabulary" {
+        let vocabulary = Vocabulary::load(&args[2])?;
+        println!(
+            "{:?}",
+            solve_stream(open_input(Path::new(&args[3]))?, &vocabulary, None)?
+        );
+        return Ok(());
+    }
+    if let Some(stream) = StreamArgs::from_args(&args)? {
+        let reader = open_input(&stream.input)?;
+        println!(
+            "{:?}",
+            solve_stream(reader, &Vocabulary::english(), stream.progress_every)?
+        );
+        return Ok(());
+    }
+
+    println!("{:?}", day_01_trebuchet(&PathBuf::from("input/01-demo-01.txt"))?);
+    println!("{:?}", day_01_trebuchet(&PathBuf::from("input/01-demo-02.txt"))?);
+    println!("{:?}", day_01_trebuchet(&PathBuf::from("input/01-demo-03.txt"))?);
+    println!("{:?}", day_01_trebuchet(&PathBuf::from("input/01.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_full_problem() {
+        let (part_one, part_two) = day_01_trebuchet(&PathBuf::from("input/01.txt")).unwrap();
+        assert_eq!(part_one, 54968);
+        assert_eq!(part_two, 54094);
+    }
+
+    #[test]
+    fn test_stream_matches_file() {
+        let lines = std::fs::read_to_string("input/01.txt").unwrap();
+        let streamed = solve_stream(lines.as_bytes(), &Vocabulary::english(), Some(100)).unwrap();
+        assert_eq!(day_01_trebuchet(&PathBuf::from("input/01.txt")).unwrap(), streamed);
+    }
+
+    #[test]
+    fn test_demo_part_two() {
+        let lines = std::fs::read_to_string("input/01-demo-02.txt").unwrap();
+        let finder = DigitFinder::new(&Vocabulary::english());
+        let codes: Vec<i64> = lines
+            .lines()
+            .map(|line| extract_code_smart(&finder, line).unwrap())
+            .collect();
+        assert_eq!(vec![29, 83, 13], codes[..3]);
+    }
+
+    #[test]
+    fn test_overlapping_and_foreign_words() {
+        let english = DigitFinder::new(&Vocabulary::english());
+        assert_eq!(Some((2, 1)), english.first_and_last("twone"));
+        assert_eq!(Some((8, 2)), english.first_and_last("xeightwo"));
+        assert_eq!(None, english.first_and_last("zero"));
+        // Multi-byte characters used to trip up the byte offset slicing.
+        assert_eq!(Some((3, 9)), english.first_and_last("ünthreeé🙂nine"));
+
+        let german = DigitFinder::new(&Vocabulary::load("german").unwrap());
+        assert_eq!(Some((5, 2)), german.first_and_last("fünfzehnzweiundzwanzig"));
+        let french = DigitFinder::new(&Vocabulary::load("french").unwrap());
+        assert_eq!(Some((3, 9)), french.first_and_last("troisixneuf"));
+        let romanian = DigitFinder::new(&Vocabulary::load("romanian").unwrap());
+        assert_eq!(Some((7, 9)), romanian.first_and_last("Șșaptenouă"));
+        assert_eq!(Some((6, 1)), romanian.first_and_last("șaseunu"));
+
+        let custom = Vocabulary::parse("uno 1\ndos 2\n\ntres 3\n").unwrap();
+        assert_eq!(Some((3, 1)), DigitFinder::new(&custom).first_and_last("xtresdosuno"));
+        assert!(Vocabulary::parse("uno").is_err());
+        assert!(Vocabulary::parse("cero 0").is_err());
+        assert!(check_input("1abc2\nzero\n").is_err());
+    }
+}
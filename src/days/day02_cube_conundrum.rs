@@ -0,0 +1,276 @@
+use crate::bench::{DayBench, Parts};
+use crate::input::{open_input, try_for_each_line, Progress, StreamArgs};
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/// Number of cubes of each color, e.g., the ones shown in a round or the ones in a bag. Colors which are not listed
+/// have zero cubes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Cubes(BTreeMap<String, usize>);
+
+impl Cubes {
+    /// Parses a list such as "3 blue, 4 red", separated by commas or newlines.
+    fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut counts = BTreeMap::new();
+        for sub in spec
+            .split([',', '\n'])
+            .map(|sub| sub.trim())
+            .filter(|sub| !sub.is_empty())
+        {
+            let name_val: Vec<&str> = sub.split_whitespace().collect();
+            let [val, name] = name_val[..] else {
+                anyhow::bail!("Expected '<count> <color>', got {:?}", sub);
+            };
+            let val: usize = val.parse().with_context(|| format!("Parse cube count in {:?}", sub))?;
+            anyhow::ensure!(
+                counts.insert(name.to_string(), val).is_none(),
+                "Color {} is listed twice in {:?}",
+                name,
+                spec
+            );
+        }
+        Ok(Cubes(counts))
+    }
+
+    fn count(&self, color: &str) -> usize {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    /// Whether these cubes could all have been drawn from 'bag' at once.
+    fn fits_in(&self, bag: &Cubes) -> bool {
+        self.0.iter().all(|(color, n)| *n <= bag.count(color))
+    }
+
+    /// The product of the cube counts of the given colors.
+    fn power<'a>(&self, colors: impl Iterator<Item = &'a String>) -> usize {
+        colors.map(|color| self.count(color)).product()
+    }
+}
+
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(|(color, n)| format!("{} {}", n, color)).collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+struct Game {
+    id: usize,
+    rounds: Vec<Cubes>,
+}
+
+impl Game {
+    fn is_possible_with(&self, bag: &Cubes) -> bool {
+        self.rounds.iter().all(|round| round.fits_in(bag))
+    }
+
+    /// The smallest bag which could have produced every round of the game.
+    fn minimum_bag(&self) -> Cubes {
+        let mut mins: BTreeMap<String, usize> = BTreeMap::new();
+        for (color, n) in self.rounds.iter().flat_map(|round| round.0.iter()) {
+            let min = mins.entry(color.clone()).or_insert(0);
+            *min = (*min).max(*n);
+        }
+        Cubes(mins)
+    }
+}
+
+fn parse_game_line(line: &str) -> anyhow::Result<Game> {
+    let (meta_chunk, rounds_chunk) = line
+        .split_once(':')
+        .with_context(|| format!("Expected 'Game <id>: <rounds>', got {:?}", line))?;
+    let game_id = meta_chunk
+        .trim()
+        .strip_prefix("Game ")
+        .and_then(|id| id.parse::<usize>().ok())
+        .with_context(|| format!("Parse game ID from {:?}", line))?;
+
+    let rounds: Vec<Cubes> = rounds_chunk
+        .trim()
+        .split_terminator(';')
+        .map(|chunk| Cubes::parse(chunk).with_context(|| format!("Parse round {:?}", chunk)))
+        .collect::<anyhow::Result<_>>()?;
+    Ok(Game { id: game_id, rounds })
+}
+
+/// The bag from Part One.
+fn default_bag() -> Cubes {
+    Cubes::parse("12 red, 13 green, 14 blue").expect("Valid default bag")
+}
+
+/// Sums the IDs of the games which are possible with 'bag' and the power scores of all games, one game per line.
+///
+/// The power score of a game is the product of its minimum bag's counts for the colors in 'bag', so the cubes of a color
+/// which is not in the bag make a game impossible but do not change its power.
+fn solve_stream(reader: impl BufRead, bag: &Cubes, progress_every: Option<usize>) -> anyhow::Result<(usize, usize)> {
+    let mut progress = Progress::new(progress_every);
+    let (mut part_one_code, mut game_power_total_part_two) = (0usize, 0usize);
+    try_for_each_line(reader, |line| {
+        let game = parse_game_line(line)?;
+        // The Part 1 solution is just the sum of the valid game IDs.
+        if game.is_possible_with(bag) {
+            part_one_code += game.id;
+        }
+        // The Part 2 solution is the sum of all games' power scores.
+        game_power_total_part_two += game.minimum_bag().power(bag.0.keys());
+        progress.tick(|| (part_one_code, game_power_total_part_two));
+        Ok(())
+    })?;
+    Ok((part_one_code, game_power_total_part_two))
+}
+
+fn read_games(fpath: &Path) -> anyhow::Result<Vec<Game>> {
+    let mut games = Vec::new();
+    try_for_each_line(open_input(fpath)?, |line| {
+        games.push(parse_game_line(line)?);
+        Ok(())
+    })?;
+    Ok(games)
+}
+
+/// Parses an input without solving it, one game per line.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    try_for_each_line(in_txt.as_bytes(), |line| parse_game_line(line).map(drop))
+}
+
+/// Times parsing the games, then summing the possible game IDs and the powers with the default bag.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 2,
+        input: "input/02.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: in_txt
+                    .lines()
+                    .map(parse_game_line)
+                    .collect::<anyhow::Result<Vec<Game>>>()?,
+                part_one: |games| {
+                    let bag = default_bag();
+                    Ok(games
+                        .iter()
+                        .filter(|game| game.is_possible_with(&bag))
+                        .map(|game| game.id)
+                        .sum::<usize>())
+                },
+                part_two: Some(|games| {
+                    let bag = default_bag();
+                    Ok(games
+                        .iter()
+                        .map(|game| game.minimum_bag().power(bag.0.keys()))
+                        .sum::<usize>())
+                }),
+            }))
+        },
+    }
+}
+
+fn day_02_cube_conundrum(fpath: &Path) -> anyhow::Result<(usize, usize)> {
+    open_input(fpath).and_then(|reader| solve_stream(reader, &default_bag(), None))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Options:
+    //  '--bag <spec>' or '--bag-file <path>' replace the Part One bag, e.g., '--bag "12 red, 13 green, 5 yellow"'.
+    //  '--possible <input>' lists the games which are possible with the bag.
+    //  '--min-bags <input>' lists the smallest possible bag for each game.
+    //  '--stream <path|-> [--progress <n_lines>]' solves a (possibly huge) input line by line.
+    let mut args: Vec<String> = std::env::args().collect();
+    let mut bag = default_bag();
+    if args.len() >= 3 && (args[1] == "--bag" || args[1] == "--bag-file") {
+        let spec = match args[1].as_str() {
+            "--bag" => args[2].clone(),
+            _ => std::fs::read_to_string(&args[2]).with_context(|| format!("Read bag from {:?}", args[2]))?,
+        };
+        bag = Cubes::parse(&spec)?;
+        args.drain(1..3);
+    }
+
+    if args.len() == 3 && args[1] == "--possible" {
+        for game in read_games(Path::new(&args[2]))?
+            .iter()
+            .filter(|g| g.is_possible_with(&bag))
+        {
+            println!("Game {}", game.id);
+        }
+        return Ok(());
+    }
+    if args.len() == 3 && args[1] == "--min-bags" {
+        for game in read_games(Path::new(&args[2]))? {
+            println!("Game {}: {}", game.id, game.minimum_bag());
+        }
+        return Ok(());
+    }
+    if let Some(stream) = StreamArgs::from_args(&args)? {
+        println!(
+            "{:?}",
+            solve_stream(open_input(&stream.input)?, &bag, stream.progress_every)?
+        );
+        return Ok(());
+    }
+    if args.len() == 2 {
+        println!("{:?}", solve_stream(open_input(Path::new(&args[1]))?, &bag, None)?);
+        return Ok(());
+    }
+
+    println!("{:?}", day_02_cube_conundrum(&PathBuf::from("input/02-demo.txt"))?);
+    println!("{:?}", day_02_cube_conundrum(&PathBuf::from("input/02.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_full_problem() {
+        let (part_one, part_two) = day_02_cube_conundrum(&PathBuf::from("input/02.txt")).unwrap();
+        assert_eq!(part_one, 2176);
+        assert_eq!(part_two, 63700);
+    }
+
+    #[test]
+    fn test_stream_matches_file() {
+        let lines = std::fs::read_to_string("input/02.txt").unwrap();
+        assert_eq!(
+            (2176, 63700),
+            solve_stream(lines.as_bytes(), &default_bag(), Some(10)).unwrap()
+        );
+        assert_eq!(
+            (8, 2286),
+            day_02_cube_conundrum(&PathBuf::from("input/02-demo.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_custom_bags_and_colors() {
+        let games = read_games(Path::new("input/02-demo.txt")).unwrap();
+        let possible: Vec<usize> = games
+            .iter()
+            .filter(|g| g.is_possible_with(&default_bag()))
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(vec![1, 2, 5], possible);
+        assert_eq!("6 blue, 13 green, 20 red", games[2].minimum_bag().to_string());
+
+        let logs = "Game 1: 3 blue, 2 yellow; 1 red\nGame 2: 4 red, 1 blue\nGame 3: 1 purple\n";
+        let with_yellow = Cubes::parse("4 red\n3 blue\n2 yellow").unwrap();
+        // Game 1 needs the yellow cubes, and nobody has purple ones. Game 2 shows no yellow, so it has zero power when
+        // yellow counts towards it.
+        assert_eq!((1 + 2, 6), solve_stream(logs.as_bytes(), &with_yellow, None).unwrap());
+        let without_yellow = Cubes::parse("4 red, 3 blue").unwrap();
+        assert_eq!(
+            (2, 3 + 4),
+            solve_stream(logs.as_bytes(), &without_yellow, None).unwrap()
+        );
+
+        assert!(Cubes::parse("3 blue, 4").is_err());
+        assert!(Cubes::parse("x blue").is_err());
+        assert!(Cubes::parse("3 blue, 2 blue").is_err());
+        assert!(parse_game_line("Game 1 3 blue").is_err());
+        assert!(parse_game_line("Game x: 3 blue").is_err());
+        assert!(check_input("Game 1: 3 blue\nGame 2: 3 blue; 1 red, 2\n").is_err());
+    }
+}
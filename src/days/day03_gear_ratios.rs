@@ -0,0 +1,326 @@
+use crate::bench::{DayBench, Parts};
+use crate::check::{DayCheck, Solver};
+use anyhow::Context;
+use rand::Rng;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug)]
+struct Number {
+    value: usize,
+    row: usize,
+    start_idx: usize,
+    end_idx: usize,
+}
+
+#[derive(Debug)]
+struct Symbol {
+    symbol: char,
+    row: usize,
+    col: usize,
+}
+
+impl Number {
+    fn borders(&self, row: usize, col: usize) -> bool {
+        (self.row).abs_diff(row) <= 1 && col >= self.start_idx.saturating_sub(1) && col <= (self.end_idx + 1)
+    }
+}
+
+fn parse_number(chrs: &[char], start_idx: usize) -> anyhow::Result<(usize, usize)> {
+    let mut cur = 0usize;
+    let mut idx = start_idx;
+    while let Some(digit) = chrs[idx].to_digit(10) {
+        cur = cur
+            .checked_mul(10)
+            .and_then(|cur| cur.checked_add(digit as usize))
+            .with_context(|| format!("Number at column {} does not fit in usize", start_idx))?;
+        idx += 1;
+        if idx >= chrs.len() {
+            break;
+        }
+    }
+    Ok((cur, idx))
+}
+
+fn parse_schematic(lines: &str) -> anyhow::Result<(Vec<Number>, Vec<Symbol>)> {
+    let mut numbers = vec![];
+    let mut symbols = vec![];
+    for (row_idx, row) in lines.split_terminator("\n").enumerate() {
+        let mut col = 0;
+        let chrs: Vec<char> = row.chars().collect();
+        while col < chrs.len() {
+            let ch = chrs[col];
+            if ch.is_ascii_digit() {
+                let old_col = col;
+                let (num, new_col) = parse_number(&chrs, col).with_context(|| format!("Row {}", row_idx + 1))?;
+                numbers.push(Number {
+                    value: num,
+                    row: row_idx,
+                    start_idx: old_col,
+                    end_idx: new_col - 1,
+                });
+                col = new_col;
+            } else if ch != '.' {
+                symbols.push(Symbol {
+                    row: row_idx,
+                    col,
+                    symbol: ch,
+                });
+                col += 1;
+            } else {
+                col += 1;
+            }
+        }
+    }
+
+    Ok((numbers, symbols))
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_schematic(in_txt).map(drop)
+}
+
+/// A grid-cell index over a parsed schematic, so that finding the numbers around a symbol only looks at its 8
+/// neighboring cells instead of at every number.
+struct SchematicIndex {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// For every cell, the index of the number covering it, if any. Rows may have different lengths.
+    number_at: Vec<Vec<Option<usize>>>,
+}
+
+impl SchematicIndex {
+    fn new(numbers: Vec<Number>, symbols: Vec<Symbol>) -> Self {
+        let mut number_at: Vec<Vec<Option<usize>>> = Vec::new();
+        for (number_idx, number) in numbers.iter().enumerate() {
+            if number_at.len() <= number.row {
+                number_at.resize(number.row + 1, Vec::new());
+            }
+            let row = &mut number_at[number.row];
+            if row.len() <= number.end_idx {
+                row.resize(number.end_idx + 1, None);
+            }
+            row[number.start_idx..=number.end_idx].fill(Some(number_idx));
+        }
+        SchematicIndex {
+            numbers,
+            symbols,
+            number_at,
+        }
+    }
+
+    /// Returns the indices of the distinct numbers bordering the cell at ('row', 'col').
+    fn adjacent_numbers(&self, row: usize, col: usize) -> Vec<usize> {
+        let mut found: Vec<usize> = Vec::new();
+        for r in row.saturating_sub(1)..=row + 1 {
+            let Some(cells) = self.number_at.get(r) else {
+                continue;
+            };
+            for c in col.saturating_sub(1)..=col + 1 {
+                if let Some(Some(number_idx)) = cells.get(c) {
+                    // Neighboring cells of the same number are always consecutive.
+                    if found.last() != Some(number_idx) {
+                        found.push(*number_idx);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Returns the part numbers, i.e., the numbers bordering any symbol.
+    fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        let mut is_part = vec![false; self.numbers.len()];
+        for symbol in &self.symbols {
+            for number_idx in self.adjacent_numbers(symbol.row, symbol.col) {
+                is_part[number_idx] = true;
+            }
+        }
+        self.numbers
+            .iter()
+            .zip(is_part)
+            .filter(|(_, is_part)| *is_part)
+            .map(|(number, _)| number)
+    }
+
+    /// Returns every 'symbol' bordering exactly 'n_adjacent' numbers, along with those numbers. Gears are '*' symbols
+    /// with two numbers.
+    fn query(&self, symbol: char, n_adjacent: usize) -> impl Iterator<Item = (&Symbol, Vec<&Number>)> {
+        self.symbols
+            .iter()
+            .filter(move |s| s.symbol == symbol)
+            .filter_map(move |s| {
+                let adjacent = self.adjacent_numbers(s.row, s.col);
+                (adjacent.len() == n_adjacent).then(|| (s, adjacent.iter().map(|idx| &self.numbers[*idx]).collect()))
+            })
+    }
+
+    /// Sums the products of the numbers around each match of a query, e.g., the gear ratios for ('*', 2).
+    fn ratio_sum(&self, symbol: char, n_adjacent: usize) -> usize {
+        self.query(symbol, n_adjacent)
+            .map(|(_, numbers)| numbers.iter().map(|n| n.value).product::<usize>())
+            .sum()
+    }
+}
+
+/// Solves both parts with the index, one lookup per symbol.
+fn solve_indexed(lines: &str) -> anyhow::Result<(usize, usize)> {
+    let (numbers, symbols) = parse_schematic(lines)?;
+    let schematic = SchematicIndex::new(numbers, symbols);
+    let part_one_sol = schematic.part_numbers().map(|n| n.value).sum();
+    let gear_ids = schematic.ratio_sum('*', 2);
+
+    Ok((part_one_sol, gear_ids))
+}
+
+/// The original quadratic search, which checks every number against every symbol.
+fn solve_naive(lines: &str) -> anyhow::Result<(usize, usize)> {
+    let (numbers, symbols) = parse_schematic(lines)?;
+    let part_one_sol = numbers
+        .iter()
+        .filter(|n| symbols.iter().any(|s| n.borders(s.row, s.col)))
+        .map(|n| n.value)
+        .sum();
+    let gear_ids = symbols
+        .iter()
+        .filter(|s| s.symbol == '*')
+        .map(|s| {
+            numbers
+                .iter()
+                .filter(|n| n.borders(s.row, s.col))
+                .collect::<Vec<&Number>>()
+        })
+        .filter(|matched_numbers| matched_numbers.len() == 2)
+        .map(|matched_numbers| matched_numbers[0].value * matched_numbers[1].value)
+        .sum();
+    Ok((part_one_sol, gear_ids))
+}
+
+fn read_schematic(input_fpath: &Path) -> anyhow::Result<SchematicIndex> {
+    let lines = crate::input::read_input(input_fpath)?;
+    let (numbers, symbols) =
+        parse_schematic(&lines).with_context(|| format!("Parse schematic from {:?}", input_fpath))?;
+    Ok(SchematicIndex::new(numbers, symbols))
+}
+
+fn day_03_gear_ratios(input_fpath: &Path) -> anyhow::Result<(usize, usize)> {
+    let lines = crate::input::read_input(input_fpath)?;
+    solve_indexed(&lines).with_context(|| format!("Parse schematic from {:?}", input_fpath))
+}
+
+/// Times parsing and indexing the schematic, then summing the part numbers and the gear ratios.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 3,
+        input: "input/03.txt",
+        parse: |in_txt| {
+            let (numbers, symbols) = parse_schematic(in_txt)?;
+            Ok(Box::new(Parts {
+                input: SchematicIndex::new(numbers, symbols),
+                part_one: |schematic| Ok(schematic.part_numbers().map(|n| n.value).sum::<usize>()),
+                part_two: Some(|schematic| Ok(schematic.ratio_sum('*', 2))),
+            }))
+        },
+    }
+}
+
+/// Checks the index against the quadratic search on small random schematics.
+pub fn cross_check() -> DayCheck {
+    DayCheck {
+        day: 3,
+        reference: Solver {
+            name: "naive",
+            solve: |lines| format!("{:?}", solve_naive(lines).map_err(|e| e.to_string())),
+        },
+        optimized: vec![Solver {
+            name: "indexed",
+            solve: |lines| format!("{:?}", solve_indexed(lines).map_err(|e| e.to_string())),
+        }],
+        random_input: |rng| {
+            let (n_rows, n_cols) = (rng.gen_range(1..=8), rng.gen_range(1..=10));
+            crate::gen::engine_schematic(rng, n_rows, n_cols)
+        },
+    }
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--query <symbol> <n_adjacent> <input>' to sum the products of the numbers around every 'symbol' which borders
+    // exactly 'n_adjacent' numbers.
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() == 5 && args[1] == "--query" {
+        let symbol = args[2].chars().next().context("Expected a symbol to query")?;
+        let n_adjacent: usize = args[3]
+            .parse()
+            .with_context(|| format!("Parse the number of adjacent numbers from {:?}", args[3]))?;
+        let schematic = read_schematic(Path::new(&args[4]))?;
+        let n_matches = schematic.query(symbol, n_adjacent).count();
+        println!("{} matches, {}", n_matches, schematic.ratio_sum(symbol, n_adjacent));
+        return Ok(());
+    }
+
+    println!("{:?}", day_03_gear_ratios(&PathBuf::from("input/03-demo.txt"))?);
+    println!("{:?}", day_03_gear_ratios(&PathBuf::from("input/03.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_full_problem() {
+        assert_eq!(
+            (4361, 467835),
+            day_03_gear_ratios(&PathBuf::from("input/03-demo.txt")).unwrap()
+        );
+        assert_eq!(
+            (551094, 80179647),
+            day_03_gear_ratios(&PathBuf::from("input/03.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_index_matches_naive_search() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut lines = String::new();
+        for _ in 0..200 {
+            let row: String = (0..150)
+                .map(|_| match rng.gen_range(0..10) {
+                    0..=3 => '.',
+                    4..=7 => char::from_digit(rng.gen_range(0..10), 10).unwrap(),
+                    8 => '*',
+                    _ => '#',
+                })
+                .collect();
+            lines.push_str(&row);
+            lines.push('\n');
+        }
+        assert_eq!(solve_naive(&lines).unwrap(), solve_indexed(&lines).unwrap());
+
+        // Generalized queries: a symbol surrounded by numbers on all sides, and one touching a single wide number.
+        let (numbers, symbols) = parse_schematic("1.2.3\n.4#5.\n6.7.8\n..$..\n.123.\n").unwrap();
+        let schematic = SchematicIndex::new(numbers, symbols);
+        assert_eq!(4 * 5 * 2 * 7, schematic.ratio_sum('#', 4));
+        assert_eq!(0, schematic.ratio_sum('#', 2));
+        assert_eq!(1, schematic.query('$', 2).count());
+        assert_eq!(
+            vec![7, 123],
+            schematic
+                .query('$', 2)
+                .next()
+                .unwrap()
+                .1
+                .iter()
+                .map(|n| n.value)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_oversized_number_is_an_error() {
+        assert!(check_input("..*\n18446744073709551615\n").is_ok());
+        assert!(check_input("..*\n18446744073709551616\n").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::bench::{DayBench, Parts};
+use crate::input::{open_input, try_for_each_line, Progress, StreamArgs};
+use anyhow::Context;
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+struct Scratchcard {
+    winning: HashSet<u64>,
+    owned: HashSet<u64>,
+}
+
+impl Scratchcard {
+    fn get_winning_numbers(&self) -> HashSet<u64> {
+        self.winning.intersection(&self.owned).cloned().collect()
+    }
+}
+
+/// Parses a scratchcard specification string of the form "Card K: A B C | D E" where A.. are non-negative integers.
+fn parse_scratchcard(line: &str) -> anyhow::Result<Scratchcard> {
+    let (_, useful_chunk) = line
+        .split_once(':')
+        .with_context(|| format!("Expected 'Card <id>: <numbers>', got {:?}", line))?;
+    let (winning_chunk, owned_chunk) = useful_chunk
+        .split_once('|')
+        .with_context(|| format!("Expected '<winning> | <owned>', got {:?}", useful_chunk))?;
+    let parse_numbers = |chunk: &str, kind: &str| -> anyhow::Result<HashSet<u64>> {
+        chunk
+            .split_whitespace()
+            .map(|nr| nr.parse().with_context(|| format!("Parse {} number {:?}", kind, nr)))
+            .collect()
+    };
+    Ok(Scratchcard {
+        winning: parse_numbers(winning_chunk, "winning")?,
+        owned: parse_numbers(owned_chunk, "owned")?,
+    })
+}
+
+/// Points for a card with 'winners' winning numbers: none for no winners, then one point doubled for each further
+/// winner. Errors out instead of overflowing.
+fn card_points(winners: usize) -> anyhow::Result<u128> {
+    match winners {
+        0 => Ok(0),
+        n => u32::try_from(n - 1)
+            .ok()
+            .and_then(|exp| 1u128.checked_shl(exp))
+            .ok_or_else(|| anyhow::anyhow!("A card with {} winning numbers is worth too many points", winners)),
+    }
+}
+
+/// What to do with copies won of cards past the end of the table. The puzzle promises this never happens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PastEnd {
+    /// Drop them, since the cards they would copy do not exist.
+    Discard,
+    /// Treat the input as invalid.
+    Reject,
+}
+
+/// Running totals for both parts. Part Two only needs to remember how many extra copies the next few cards have won,
+/// so a window as long as the most winning numbers seen so far replaces the table of multipliers.
+#[derive(Default)]
+struct CardLedger {
+    n_cards: usize,
+    points: u128,
+    instances: u128,
+    extra_copies: VecDeque<u128>,
+}
+
+impl CardLedger {
+    /// Records the next card, which has 'winners' winning numbers.
+    fn push(&mut self, winners: usize) -> anyhow::Result<()> {
+        self.n_cards += 1;
+        self.points = self
+            .points
+            .checked_add(card_points(winners)?)
+            .ok_or_else(|| anyhow::anyhow!("Total points overflow at card {}", self.n_cards))?;
+
+        let cur_mult = 1 + self.extra_copies.pop_front().unwrap_or(0);
+        self.instances = self
+            .instances
+            .checked_add(cur_mult)
+            .ok_or_else(|| anyhow::anyhow!("Total cards overflow at card {}", self.n_cards))?;
+        if self.extra_copies.len() < winners {
+            self.extra_copies.resize(winners, 0);
+        }
+        for copies in self.extra_copies.iter_mut().take(winners) {
+            *copies = copies
+                .checked_add(cur_mult)
+                .ok_or_else(|| anyhow::anyhow!("Card copies overflow at card {}", self.n_cards))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the total points and the total number of cards, applying 'past_end' to copies of missing cards.
+    fn finish(self, past_end: PastEnd) -> anyhow::Result<(u128, u128)> {
+        if past_end == PastEnd::Reject {
+            if let Some(offset) = self.extra_copies.iter().position(|copies| *copies > 0) {
+                anyhow::bail!(
+                    "Won copies of card {}, but there are only {} cards",
+                    self.n_cards + offset + 1,
+                    self.n_cards
+                );
+            }
+        }
+        Ok((self.points, self.instances))
+    }
+}
+
+/// Scores cards one line at a time.
+fn solve_stream(
+    reader: impl BufRead,
+    past_end: PastEnd,
+    progress_every: Option<usize>,
+) -> anyhow::Result<(u128, u128)> {
+    let mut progress = Progress::new(progress_every);
+    let mut ledger = CardLedger::default();
+    try_for_each_line(reader, |line| {
+        let winners = parse_scratchcard(line)?.get_winning_numbers().len();
+        ledger.push(winners)?;
+        progress.tick(|| (ledger.points, ledger.instances));
+        Ok(())
+    })?;
+    ledger.finish(past_end)
+}
+
+/// Parses an input without solving it, one card per line.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    try_for_each_line(in_txt.as_bytes(), |line| parse_scratchcard(line).map(drop))
+}
+
+/// Times parsing the cards, then scoring them and counting the copies won.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 4,
+        input: "input/04.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: in_txt
+                    .lines()
+                    .map(|line| parse_scratchcard(line).map(|card| card.get_winning_numbers().len()))
+                    .collect::<anyhow::Result<Vec<usize>>>()?,
+                part_one: |winners| {
+                    winners
+                        .iter()
+                        .map(|winners| card_points(*winners))
+                        .sum::<anyhow::Result<u128>>()
+                },
+                part_two: Some(|winners| {
+                    let mut ledger = CardLedger::default();
+                    for winners in winners {
+                        ledger.push(*winners)?;
+                    }
+                    ledger.finish(PastEnd::Reject)
+                }),
+            }))
+        },
+    }
+}
+
+fn day_04_scratchcards(input_fpath: &Path) -> anyhow::Result<(u128, u128)> {
+    open_input(input_fpath).and_then(|reader| solve_stream(reader, PastEnd::Reject, None))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Options:
+    //  '--discard-past-end' drops copies of cards past the end of the table instead of rejecting the input.
+    //  '--stream <path|-> [--progress <n_lines>]' solves a (possibly huge) input line by line.
+    let mut args: Vec<String> = std::env::args().collect();
+    let mut past_end = PastEnd::Reject;
+    if args.len() >= 2 && args[1] == "--discard-past-end" {
+        past_end = PastEnd::Discard;
+        args.remove(1);
+    }
+    if let Some(stream) = StreamArgs::from_args(&args)? {
+        println!(
+            "{:?}",
+            solve_stream(open_input(&stream.input)?, past_end, stream.progress_every)?
+        );
+        return Ok(());
+    }
+
+    println!("{:?}", day_04_scratchcards(&PathBuf::from("input/04-demo.txt"))?);
+    println!("{:?}", day_04_scratchcards(&PathBuf::from("input/04.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_full_problem() {
+        assert_eq!(
+            (13, 30),
+            day_04_scratchcards(&PathBuf::from("input/04-demo.txt")).unwrap()
+        );
+        assert_eq!(
+            (26346, 8467762),
+            day_04_scratchcards(&PathBuf::from("input/04.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scoring_edge_cases() {
+        assert_eq!(0, card_points(0).unwrap());
+        assert_eq!(1, card_points(1).unwrap());
+        assert_eq!(8, card_points(4).unwrap());
+        assert_eq!(1u128 << 127, card_points(128).unwrap());
+        assert!(card_points(129).is_err());
+
+        // The second card wins no numbers, and the last card wins copies of two cards which do not exist.
+        let cards = "Card 1: 1 2 | 1 3\nCard 2: 4 5 | 6 7\nCard 3: 8 9 | 8 9\n";
+        assert_eq!(
+            (1 + 2, 1 + 2 + 1),
+            solve_stream(cards.as_bytes(), PastEnd::Discard, None).unwrap()
+        );
+        let err = solve_stream(cards.as_bytes(), PastEnd::Reject, None).unwrap_err();
+        assert!(err.to_string().contains("card 4"), "{}", err);
+
+        assert!(check_input("Card 1: 1 2 | 1 3\nCard 2: 4 5\n").is_err());
+        assert!(check_input("Card 1 1 2 | 1 3\n").is_err());
+        assert!(check_input("Card 1: 1 -2 | 1 3\n").is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use crate::bench::{DayBench, Parts};
+use crate::check::{DayCheck, Solver};
+use anyhow::Context;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+struct Rule {
+    dst_start: usize,
+    src_start: usize,
+    length: usize,
+}
+
+impl Rule {
+    fn applies(&self, input: usize) -> bool {
+        input >= self.src_start && input < self.src_start + self.length
+    }
+
+    fn apply(&self, input: usize) -> usize {
+        let offset = input - self.src_start;
+        self.dst_start + offset
+    }
+}
+
+#[derive(Debug)]
+struct Mapping {
+    rules: Vec<Rule>,
+}
+
+impl Mapping {
+    fn apply(&self, input: usize) -> usize {
+        // We can speed it up by pre-sorting the rules.
+        // Assumes only one rule can apply to an input.
+        for rule in &self.rules {
+            if rule.applies(input) {
+                return rule.apply(input);
+            }
+        }
+
+        input
+    }
+
+    /// Maps every half-open range of inputs to the ranges of outputs they cover, splitting them at rule boundaries.
+    fn apply_ranges(&self, ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let mut sorted_rules: Vec<&Rule> = self.rules.iter().collect();
+        sorted_rules.sort_by_key(|rule| rule.src_start);
+
+        let mut out = vec![];
+        for &(mut start, end) in ranges {
+            for rule in &sorted_rules {
+                let rule_end = rule.src_start + rule.length;
+                if start >= end || rule.src_start >= end {
+                    break;
+                }
+                if rule_end <= start {
+                    continue;
+                }
+                // Inputs before the rule map to themselves.
+                if start < rule.src_start {
+                    out.push((start, rule.src_start));
+                    start = rule.src_start;
+                }
+                let stop = end.min(rule_end);
+                out.push((rule.apply(start), rule.apply(start) + (stop - start)));
+                start = stop;
+            }
+            if start < end {
+                out.push((start, end));
+            }
+        }
+        out
+    }
+}
+
+/// Parses a space-separated list of numbers.
+fn parse_numbers(line: &str) -> anyhow::Result<Vec<usize>> {
+    line.split(' ')
+        .map(|nr| {
+            nr.parse::<usize>()
+                .with_context(|| format!("Parse number {:?} in {:?}", nr, line))
+        })
+        .collect()
+}
+
+fn parse_into_mapping(lines: &[&str], start_idx: usize) -> anyhow::Result<(Mapping, usize)> {
+    let mut cur_idx = start_idx;
+    let mut rules = vec![];
+    while cur_idx < lines.len() {
+        if lines[cur_idx].trim().is_empty() {
+            break;
+        }
+        let numbers = parse_numbers(lines[cur_idx])?;
+        let [dst_start, src_start, length] = numbers[..] else {
+            anyhow::bail!("Expected '<dst> <src> <length>', got {:?}", lines[cur_idx]);
+        };
+        // Both ranges must fit, since mapping adds offsets to their starts.
+        anyhow::ensure!(
+            dst_start.checked_add(length).is_some() && src_start.checked_add(length).is_some(),
+            "Rule {:?} overflows",
+            lines[cur_idx]
+        );
+        rules.push(Rule {
+            dst_start,
+            src_start,
+            length,
+        });
+        cur_idx += 1;
+    }
+
+    Ok((Mapping { rules }, cur_idx))
+}
+
+struct Almanac {
+    seed_ids: Vec<usize>,
+    mappings: Vec<Mapping>,
+}
+
+impl Almanac {
+    fn location(&self, seed: usize) -> usize {
+        self.mappings.iter().fold(seed, |id, mapping| mapping.apply(id))
+    }
+
+    /// The Part Two seeds, read as pairs of (start, length).
+    fn seed_ranges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.seed_ids.chunks(2).map(|pair| (pair[0], pair[0] + pair[1]))
+    }
+}
+
+fn parse_almanac(in_txt: &str) -> anyhow::Result<Almanac> {
+    let lines: Vec<&str> = in_txt.split('\n').collect();
+
+    let seed_line = lines[0]
+        .strip_prefix("seeds: ")
+        .with_context(|| format!("Expected 'seeds: <ids>', got {:?}", lines[0]))?;
+    let seed_ids = parse_numbers(seed_line)?;
+    // Part Two reads the seeds as (start, length) pairs.
+    anyhow::ensure!(
+        seed_ids.len() % 2 == 0 && seed_ids.chunks(2).all(|pair| pair[0].checked_add(pair[1]).is_some()),
+        "Expected (start, length) seed pairs, got {:?}",
+        seed_line
+    );
+
+    // Seed to soil, soil to fertilizer, fertilizer to water, water to light, light to temperature, temperature to
+    // humidity, and humidity to location.
+    let mut mappings = vec![];
+    let mut next_idx = 1;
+    for _ in 0..7 {
+        let (mapping, end_idx) =
+            parse_into_mapping(&lines, next_idx + 2).with_context(|| format!("Mapping {}", mappings.len() + 1))?;
+        mappings.push(mapping);
+        next_idx = end_idx;
+    }
+
+    Ok(Almanac { seed_ids, mappings })
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_almanac(in_txt).map(drop)
+}
+
+/// Part 2, slow AF version - we just generate an effective list of seeds and brute-force it through our mappings.
+/// Takes about 1 min on an M1 mac with optimized Rust. Of course, in Python this would likely take >15 min, forcing
+/// you to do it right, namely implement the "apply" of a range to function on ranges!
+fn lowest_range_location_brute_force(almanac: &Almanac) -> usize {
+    almanac
+        .seed_ranges()
+        .flat_map(|(start, end)| start..end)
+        .map(|seed| almanac.location(seed))
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+/// Part 2 done right: pushes whole ranges of seeds through the mappings, so the work only depends on the number of
+/// rules, not on how many seeds there are.
+fn lowest_range_location(almanac: &Almanac) -> usize {
+    let seed_ranges: Vec<(usize, usize)> = almanac.seed_ranges().collect();
+    almanac
+        .mappings
+        .iter()
+        .fold(seed_ranges, |ranges, mapping| mapping.apply_ranges(&ranges))
+        .iter()
+        .map(|(start, _)| *start)
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+/// Part 1: the lowest location number that corresponds to any of the initial seeds.
+fn lowest_location(almanac: &Almanac) -> usize {
+    almanac
+        .seed_ids
+        .iter()
+        .map(|seed| almanac.location(*seed))
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+fn solve(in_txt: &str, part_two: fn(&Almanac) -> usize) -> anyhow::Result<(usize, usize)> {
+    let almanac = parse_almanac(in_txt)?;
+    Ok((lowest_location(&almanac), part_two(&almanac)))
+}
+
+fn day_05_seed(input_fpath: &PathBuf) -> anyhow::Result<(usize, usize)> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    solve(&in_txt, lowest_range_location).with_context(|| format!("Parse almanac from {:?}", input_fpath))
+}
+
+/// Times parsing the almanac, then mapping the seeds and the seed ranges.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 5,
+        input: "input/05.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: parse_almanac(in_txt)?,
+                part_one: |almanac| Ok(lowest_location(almanac)),
+                part_two: Some(|almanac| Ok(lowest_range_location(almanac))),
+            }))
+        },
+    }
+}
+
+/// Checks the range mapping against the brute force on almanacs with a few short seed ranges.
+pub fn cross_check() -> DayCheck {
+    DayCheck {
+        day: 5,
+        reference: Solver {
+            name: "brute_force",
+            solve: |in_txt| {
+                format!(
+                    "{:?}",
+                    solve(in_txt, lowest_range_location_brute_force).map_err(|e| e.to_string())
+                )
+            },
+        },
+        optimized: vec![Solver {
+            name: "ranges",
+            solve: |in_txt| format!("{:?}", solve(in_txt, lowest_range_location).map_err(|e| e.to_string())),
+        }],
+        random_input: |rng| crate::gen::almanac(rng, 3),
+    }
+}
+
+pub fn main() -> anyhow::Result<()> {
+    println!("{:?}", day_05_seed(&PathBuf::from("input/05-demo.txt"))?);
+    println!("{:?}", day_05_seed(&PathBuf::from("input/05.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_problem() {
+        assert_eq!((35, 46), day_05_seed(&PathBuf::from("input/05-demo.txt")).unwrap());
+        let in_txt = std::fs::read_to_string("input/05-demo.txt").unwrap();
+        assert_eq!((35, 46), solve(&in_txt, lowest_range_location_brute_force).unwrap());
+        // The brute force agrees on the full input too, after a few minutes in Release mode.
+        assert_eq!(
+            (177942185, 69841803),
+            day_05_seed(&PathBuf::from("input/05.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_malformed_almanacs() {
+        let in_txt = std::fs::read_to_string("input/05-demo.txt").unwrap();
+        assert!(check_input(&in_txt).is_ok());
+        assert!(check_input(&in_txt.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1)).is_err());
+        assert!(check_input(&in_txt.replacen("50 98 2", "50 98", 1)).is_err());
+        assert!(check_input(&in_txt.replacen("50 98 2", "50 18446744073709551615 2", 1)).is_err());
+        assert!(check_input(&in_txt.replacen("seeds: ", "seeds:", 1)).is_err());
+        assert!(check_input("").is_err());
+    }
+}
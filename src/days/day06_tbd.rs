@@ -0,0 +1,117 @@
+use crate::bench::{DayBench, Parts};
+use anyhow::Context;
+use std::path::PathBuf;
+
+fn get_travel_dist(hold_time: usize, total_time: usize) -> usize {
+    assert!(total_time >= hold_time);
+    let travel_time = total_time - hold_time;
+    let speed_mps = hold_time;
+    travel_time * speed_mps
+}
+
+fn get_successful_strategies(time: usize, distance_record_to_beat: usize) -> Vec<usize> {
+    let succ: Vec<usize> = (0..(time as u64))
+        .map(|t| get_travel_dist(t as usize, time))
+        .filter(|d| d > &distance_record_to_beat)
+        .collect();
+    succ
+}
+
+/// The race times and record distances as written, and as read with the kerning fixed, i.e., as one big race.
+struct Races {
+    times: Vec<usize>,
+    distances: Vec<usize>,
+    big_time: usize,
+    big_dist: usize,
+}
+
+/// Parses the numbers following 'label' on 'line'.
+fn parse_row(line: Option<&str>, label: &str) -> anyhow::Result<Vec<usize>> {
+    let numbers = line
+        .and_then(|line| line.strip_prefix(label))
+        .with_context(|| format!("Expected a line starting with {:?}", label))?;
+    numbers
+        .split_whitespace()
+        .map(|nr| {
+            nr.parse::<usize>()
+                .with_context(|| format!("Parse {:?} in {:?}", nr, label))
+        })
+        .collect()
+}
+
+/// Reads the digits of all 'nums' as a single number.
+fn unkern(nums: &[usize]) -> anyhow::Result<usize> {
+    let digits: String = nums.iter().map(|nr| nr.to_string()).collect();
+    digits
+        .parse()
+        .with_context(|| format!("Parse kerned number {:?}", digits))
+}
+
+fn parse_races(in_txt: &str) -> anyhow::Result<Races> {
+    let mut lines = in_txt.split('\n');
+    let times = parse_row(lines.next(), "Time:")?;
+    let distances = parse_row(lines.next(), "Distance:")?;
+    anyhow::ensure!(
+        times.len() == distances.len(),
+        "Got {} times but {} distances",
+        times.len(),
+        distances.len()
+    );
+    Ok(Races {
+        big_time: unkern(&times)?,
+        big_dist: unkern(&distances)?,
+        times,
+        distances,
+    })
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_races(in_txt).map(drop)
+}
+
+/// Part one: Compute number of ways to win for each scenario, and multiply them all together
+fn ways_to_win_product(times: &[usize], distances: &[usize]) -> usize {
+    times
+        .iter()
+        .zip(distances.iter())
+        .map(|(time, distance_record)| get_successful_strategies(*time, *distance_record).len())
+        .product()
+}
+
+/// Times parsing the races, then counting the ways to win the short races and the single long one.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 6,
+        input: "input/06.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: parse_races(in_txt)?,
+                part_one: |races| Ok(ways_to_win_product(&races.times, &races.distances)),
+                part_two: Some(|races| Ok(get_successful_strategies(races.big_time, races.big_dist).len())),
+            }))
+        },
+    }
+}
+
+fn day_06_tbd(input_fpath: &PathBuf) -> anyhow::Result<(usize, usize)> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    let Races {
+        times,
+        distances,
+        big_time,
+        big_dist,
+    } = parse_races(&in_txt).with_context(|| format!("Parse races from {:?}", input_fpath))?;
+
+    let part_one_answer = ways_to_win_product(&times, &distances);
+    // Part two: The same, except the different numbers were actually all chunks of one big number
+    let part_two_answer = get_successful_strategies(big_time, big_dist).len();
+
+    Ok((part_one_answer, part_two_answer))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    println!("{:?}", day_06_tbd(&PathBuf::from("input/06-demo.txt"))?);
+    println!("{:?}", day_06_tbd(&PathBuf::from("input/06.txt"))?);
+    Ok(())
+}
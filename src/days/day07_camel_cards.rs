@@ -0,0 +1,220 @@
+use std::cmp::{Ordering, Reverse};
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::bench::{DayBench, Parts};
+use anyhow::Context;
+use itertools::Itertools;
+
+/// The face cards, from weakest to strongest. Any digit is a valid number card.
+const FACES: &str = "TJQKA";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Card<'a> {
+    val: &'a str,
+}
+
+fn get_value(card: char, joker_enabled: bool) -> u8 {
+    if let Some(digit) = card.to_digit(10) {
+        digit as u8
+    } else {
+        match (card, joker_enabled) {
+            ('T', _) => 10,
+            // A very important distinction without it it's easy to make subtle mistakes in Part 2, since a buffed
+            // joker when it comes to getting a hand's tier gets nerfed when used to rank hands within a tier!
+            ('J', true) => 1,
+            ('J', false) => 11,
+            ('Q', _) => 12,
+            ('K', _) => 13,
+            ('A', _) => 14,
+            _ => panic!("Invalid card {}", card),
+        }
+    }
+}
+
+impl Card<'_> {
+    fn get_counts(&self) -> HashMap<char, usize> {
+        self.val.chars().counts()
+    }
+
+    fn get_card_vals(&self, joker_enabled: bool) -> Vec<u8> {
+        self.val.chars().map(|card| get_value(card, joker_enabled)).collect()
+    }
+
+    fn get_tier(&self) -> usize {
+        let mut c: Vec<(char, usize)> = self.get_counts().into_iter().collect();
+        c.sort_by_key(|e| Reverse(e.1));
+        let second_highest_count = c.get(1).map(|x| x.1).unwrap_or_default();
+
+        match (c[0].1, second_highest_count) {
+            (5, _) => 0,
+            (4, _) => 1,
+            (3, 2) => 2,
+            (3, _) => 3,
+            (2, 2) => 4,
+            (2, _) => 5,
+            _ => 6,
+        }
+    }
+
+    fn get_tier_joker(&self) -> usize {
+        let counts = self.get_counts();
+        let mut c: Vec<(char, usize)> = counts.clone().into_iter().collect();
+        c.sort_by_key(|e| Reverse(e.1));
+
+        if c[0].1 == 5 {
+            // Only one way to get a pure five-of-a-kind.
+            return 0;
+        }
+
+        let n_j = counts.get(&'J').unwrap_or(&0);
+        let c_non_j_map: HashMap<char, usize> = counts.clone().into_iter().filter(|c| c.0 != 'J').collect();
+        let mut c_non_j: Vec<(char, usize)> = c_non_j_map.clone().into_iter().collect();
+        c_non_j.sort_by_key(|e| Reverse(e.1));
+
+        let highest_nonj_count = c_non_j.first().map(|x| x.1).unwrap_or_default();
+        let highest_theoretical = highest_nonj_count + n_j;
+        let second_highest_count = c_non_j.get(1).map(|x| x.1).unwrap_or_default();
+        assert!(second_highest_count <= highest_theoretical);
+
+        match (highest_theoretical, second_highest_count) {
+            (5, _) => 0,
+            (4, _) => 1,
+            (3, 2) => 2,
+            (3, _) => 3,
+            (2, 2) => 4,
+            (2, _) => 5,
+            _ => 6,
+        }
+    }
+}
+
+impl Card<'_> {
+    fn cmp_joker(&self, other: &Self, joker_enabled: bool) -> Ordering {
+        for (v1, v2) in self
+            .get_card_vals(joker_enabled)
+            .iter()
+            .zip(other.get_card_vals(joker_enabled).iter())
+        {
+            if v1 > v2 {
+                return Ordering::Greater;
+            } else if v1 < v2 {
+                return Ordering::Less;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+fn parse_card_bid(line: &str) -> anyhow::Result<(Card<'_>, u64)> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let [hand, bid] = parts[..] else {
+        anyhow::bail!("Expected '<hand> <bid>', got {:?}", line);
+    };
+    anyhow::ensure!(
+        hand.chars().count() == 5 && hand.chars().all(|card| card.is_ascii_digit() || FACES.contains(card)),
+        "Expected five digit or {} cards, got {:?}",
+        FACES,
+        hand
+    );
+    let bid = bid.parse::<u64>().with_context(|| format!("Parse bid {:?}", bid))?;
+    Ok((Card { val: hand }, bid))
+}
+
+fn parse_card_bids(in_txt: &str) -> anyhow::Result<Vec<(Card<'_>, u64)>> {
+    in_txt
+        .split_terminator('\n')
+        .enumerate()
+        .map(|(line_idx, line)| parse_card_bid(line).with_context(|| format!("Line {}", line_idx + 1)))
+        .collect()
+}
+
+/// Parses an input without solving it, one hand and bid per line.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_card_bids(in_txt).map(drop)
+}
+
+/// Ranks all hands, weakest first, and adds up each bid times its rank.
+fn total_winnings(card_bids: &[(Card, u64)], joker_enabled: bool) -> usize {
+    let mut tiers: Vec<Vec<(&Card, u64)>> = Vec::new();
+    for _ in 0..7 {
+        tiers.push(Vec::new());
+    }
+    for (card, bid) in card_bids.iter() {
+        let tier = if joker_enabled {
+            card.get_tier_joker()
+        } else {
+            card.get_tier()
+        };
+        tiers[tier].push((card, *bid));
+    }
+    let mut all: Vec<(&Card, u64)> = Vec::new();
+    for tier in tiers.iter_mut().rev() {
+        tier.sort_by(|l, r| l.0.cmp_joker(r.0, joker_enabled));
+        let mut tc = tier.clone();
+        all.append(&mut tc);
+    }
+
+    all.iter()
+        .enumerate()
+        .map(|(idx, cv)| (idx + 1) * (cv.1 as usize))
+        .sum()
+}
+
+/// Times parsing the hands, then ranking them without and with jokers.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 7,
+        input: "input/07.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: parse_card_bids(in_txt)?,
+                part_one: |card_bids| Ok(total_winnings(card_bids, false)),
+                part_two: Some(|card_bids| Ok(total_winnings(card_bids, true))),
+            }))
+        },
+    }
+}
+
+fn day_07_camel_cards(input_fpath: &PathBuf) -> anyhow::Result<(usize, usize)> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    let card_bids: Vec<(Card, u64)> =
+        parse_card_bids(&in_txt).with_context(|| format!("Parse hands from {:?}", input_fpath))?;
+    Ok((total_winnings(&card_bids, false), total_winnings(&card_bids, true)))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    println!("{:?}", day_07_camel_cards(&PathBuf::from("input/07-demo-00.txt"))?);
+    println!("{:?}", day_07_camel_cards(&PathBuf::from("input/07-demo-01.txt"))?);
+    println!("{:?}", day_07_camel_cards(&PathBuf::from("input/07.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_full_problem() {
+        let (part_one, part_two) = day_07_camel_cards(&PathBuf::from("input/07.txt")).unwrap();
+        assert_eq!(part_one, 248836197);
+        assert_eq!(part_two, 251195607);
+    }
+
+    #[test]
+    fn test_windows_line_endings() {
+        let original = std::fs::read_to_string("input/07.txt").unwrap();
+        let fpath = std::env::temp_dir().join("aoc23-07-crlf.txt");
+        std::fs::write(&fpath, format!("\u{feff}{}\r\n", original.replace('\n', " \r\n"))).unwrap();
+        assert_eq!((248836197, 251195607), day_07_camel_cards(&fpath).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_hands() {
+        assert!(check_input("32T3K 765\nT55J5 684\n").is_ok());
+        assert!(check_input("32T3K 765\nT55J5\n").is_err());
+        assert!(check_input("32T3K 765 1\n").is_err());
+        assert!(check_input("32T3 765\n").is_err());
+        assert!(check_input("32T3X 765\n").is_err());
+        assert!(check_input("32T3K -765\n").is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::bench::{DayBench, Parts};
+use anyhow::Context;
+use std::{collections::HashMap, path::PathBuf};
+
+#[derive(Debug)]
+struct Location {
+    name: String,
+    left: String,
+    right: String,
+}
+
+fn parse_location(line: &str) -> anyhow::Result<Location> {
+    // AAA = (BBB, CCC)
+
+    let (name, children) = line
+        .split_once('=')
+        .with_context(|| format!("Expected '<name> = (<left>, <right>)', got {:?}", line))?;
+    let name = name.trim().to_string();
+    let junk: &[_] = &['(', ')', ',', ' '];
+    let (left, right) = children
+        .split_once(',')
+        .with_context(|| format!("Expected '(<left>, <right>)', got {:?}", children))?;
+
+    let left = left.trim_matches(junk).to_string();
+    let right = right.trim_matches(junk).to_string();
+
+    Ok(Location { name, left, right })
+}
+
+/// The left/right instructions, and the locations by name.
+struct Network {
+    lr_steps: Vec<char>,
+    locations: HashMap<String, Location>,
+}
+
+fn parse_network(in_txt: &str) -> anyhow::Result<Network> {
+    let lines: Vec<&str> = in_txt.split_terminator('\n').collect();
+
+    let lr_steps: Vec<char> = lines
+        .first()
+        .context("Expected the instructions on the first line")?
+        .chars()
+        .collect();
+    anyhow::ensure!(
+        !lr_steps.is_empty() && lr_steps.iter().all(|step| *step == 'L' || *step == 'R'),
+        "Expected a non-empty sequence of 'L' and 'R' instructions, got {:?}",
+        lines[0]
+    );
+    let mut locations: HashMap<String, Location> = HashMap::new();
+    for (line_idx, line) in lines.iter().enumerate().skip(2) {
+        let location = parse_location(line).with_context(|| format!("Line {}", line_idx + 1))?;
+        anyhow::ensure!(
+            !locations.contains_key(&location.name),
+            "Location {} is defined twice",
+            location.name
+        );
+        locations.insert(location.name.clone(), location);
+    }
+    // Walking the network looks every location up by name.
+    for location in locations.values() {
+        for child in [&location.left, &location.right] {
+            anyhow::ensure!(
+                locations.contains_key(child),
+                "Location {} leads to unknown {}",
+                location.name,
+                child
+            );
+        }
+    }
+
+    Ok(Network { lr_steps, locations })
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_network(in_txt).map(drop)
+}
+
+/// Counts the steps from 'start' until the first location for which 'is_end' holds.
+fn steps_until(network: &Network, start: &Location, is_end: impl Fn(&str) -> bool) -> usize {
+    let mut pos = start;
+    let mut sidx = 0;
+    let mut steps = 0;
+    while !is_end(&pos.name) {
+        if network.lr_steps[sidx] == 'L' {
+            pos = &network.locations[&pos.left];
+        } else {
+            pos = &network.locations[&pos.right];
+        }
+        steps += 1;
+        sidx = (sidx + 1) % network.lr_steps.len();
+    }
+    steps
+}
+
+/// Part 1: the steps from AAA to ZZZ, or zero for the ghost-only demo which has neither.
+fn human_steps(network: &Network) -> usize {
+    match network.locations.get("AAA") {
+        Some(start) => steps_until(network, start, |name| name == "ZZZ"),
+        None => 0,
+    }
+}
+
+/// Part 2: every ghost walks its own cycle, so they all first meet at the least common multiple of the cycles.
+fn ghost_steps(network: &Network) -> usize {
+    network
+        .locations
+        .values()
+        .filter(|l| l.name.ends_with('A'))
+        .map(|start| steps_until(network, start, |name| name.ends_with('Z')))
+        .reduce(|l, r| l * r / gcdusize(l, r))
+        .unwrap_or(0)
+}
+
+/// Times parsing the network, then walking it as a human and as the ghosts.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 8,
+        input: "input/08.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: parse_network(in_txt)?,
+                part_one: |network| Ok(human_steps(network)),
+                part_two: Some(|network| Ok(ghost_steps(network))),
+            }))
+        },
+    }
+}
+
+fn day_08_haunted_wasteland(input_fpath: &PathBuf) -> anyhow::Result<(usize, usize)> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    let network = parse_network(&in_txt).with_context(|| format!("Parse network from {:?}", input_fpath))?;
+    Ok((human_steps(&network), ghost_steps(&network)))
+}
+
+pub fn gcdusize(n: usize, m: usize) -> usize {
+    gcd(n as u64, m as u64) as usize
+}
+
+pub fn gcd(mut n: u64, mut m: u64) -> u64 {
+    assert!(n != 0 && m != 0);
+    while m != 0 {
+        if m < n {
+            std::mem::swap(&mut m, &mut n);
+        }
+        m %= n;
+    }
+    n
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // println!("{:?}", day_08_tbd(&PathBuf::from("input/08-demo-01.txt")));
+    println!(
+        "{:?}",
+        day_08_haunted_wasteland(&PathBuf::from("input/08-demo-02.txt"))?
+    );
+    println!("{:?}", day_08_haunted_wasteland(&PathBuf::from("input/08.txt"))?);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::bench::{DayBench, Parts};
+use crate::input::{open_input, try_for_each_line, Progress, StreamArgs};
+use anyhow::Context;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+fn parse_seq(line: &str) -> anyhow::Result<Vec<i64>> {
+    let seq = line
+        .split_whitespace()
+        .map(|n_str| {
+            n_str
+                .parse::<i64>()
+                .with_context(|| format!("Invalid number: {}", n_str))
+        })
+        .collect::<anyhow::Result<Vec<i64>>>()?;
+    anyhow::ensure!(!seq.is_empty(), "Expected at least one number");
+    Ok(seq)
+}
+
+fn derivative(seq: &[i64]) -> Vec<i64> {
+    seq.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// Returns the next and the previous value of a sequence, extrapolated from its derivatives.
+fn extrapolate(seq: &[i64]) -> (i64, i64) {
+    let mut derivatives: Vec<Vec<i64>> = Vec::new();
+    derivatives.push(seq.to_vec());
+
+    loop {
+        let last = derivatives.last().unwrap();
+        let all_zeros = last.iter().filter(|x| **x != 0i64).collect::<Vec<&i64>>().is_empty();
+
+        if all_zeros {
+            break;
+        }
+
+        let new_der = derivative(last);
+        derivatives.push(new_der);
+    }
+
+    let prediction: i64 = derivatives.iter().map(|seq| seq.last().expect("")).sum();
+
+    let mut prediction_front: i64 = 0;
+    for der in derivatives.iter().rev() {
+        prediction_front = der.first().expect("") - prediction_front;
+    }
+
+    (prediction, prediction_front)
+}
+
+/// Sums the forward and backward predictions of every sequence, one sequence per line.
+fn solve_stream(reader: impl BufRead, progress_every: Option<usize>) -> anyhow::Result<(i64, i64)> {
+    let mut progress = Progress::new(progress_every);
+    let (mut part_one_answer, mut part_two_answer) = (0i64, 0i64);
+    try_for_each_line(reader, |line| {
+        let (prediction, prediction_front) = extrapolate(&parse_seq(line)?);
+        part_one_answer += prediction;
+        part_two_answer += prediction_front;
+        progress.tick(|| (part_one_answer, part_two_answer));
+        Ok(())
+    })?;
+    Ok((part_one_answer, part_two_answer))
+}
+
+/// Parses an input without solving it, one sequence per line.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    try_for_each_line(in_txt.as_bytes(), |line| parse_seq(line).map(drop))
+}
+
+/// Times parsing the sequences, then extrapolating them forwards and backwards.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 9,
+        input: "input/09.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: in_txt
+                    .lines()
+                    .map(parse_seq)
+                    .collect::<anyhow::Result<Vec<Vec<i64>>>>()?,
+                part_one: |seqs| Ok(seqs.iter().map(|seq| extrapolate(seq).0).sum::<i64>()),
+                part_two: Some(|seqs| Ok(seqs.iter().map(|seq| extrapolate(seq).1).sum::<i64>())),
+            }))
+        },
+    }
+}
+
+fn day_09_mirage_maintenance(input_fpath: &Path) -> anyhow::Result<(i64, i64)> {
+    open_input(input_fpath).and_then(|reader| solve_stream(reader, None))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--stream <path|-> [--progress <n_lines>]' to solve a (possibly huge) input line by line.
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(stream) = StreamArgs::from_args(&args)? {
+        println!("{:?}", solve_stream(open_input(&stream.input)?, stream.progress_every)?);
+        return Ok(());
+    }
+
+    println!("{:?}", day_09_mirage_maintenance(&PathBuf::from("input/09-demo.txt"))?);
+    println!("{:?}", day_09_mirage_maintenance(&PathBuf::from("input/09.txt"))?);
+    Ok(())
+}
@@ -0,0 +1,507 @@
+//! AoC 2023 Day 12: Hot Springs
+
+use crate::bench::{DayBench, Parts};
+use crate::check::{DayCheck, Solver};
+use crate::input::{open_input, try_for_each_line, Progress, StreamArgs};
+use anyhow::Context;
+use rand::Rng;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+struct SpringRecord {
+    statuses: Vec<char>,
+    cluster_sizes: Vec<usize>,
+}
+
+impl SpringRecord {
+    fn multiply(&self, n: usize) -> Self {
+        SpringRecord {
+            statuses: repeat_with_sep(&self.statuses, n),
+            cluster_sizes: self.cluster_sizes.repeat(n),
+        }
+    }
+}
+
+fn repeat_with_sep(st: &[char], n: usize) -> Vec<char> {
+    let mut out = Vec::new();
+
+    for _ in 0..n - 1 {
+        out.extend_from_slice(st);
+        out.push('?');
+    }
+    out.extend_from_slice(st);
+
+    out
+}
+
+fn parse_spring_record(line: &str) -> anyhow::Result<SpringRecord> {
+    let (statuses, cluster_sizes) = line
+        .split_once(' ')
+        .with_context(|| format!("Expected '<statuses> <cluster sizes>', got {:?}", line))?;
+    if let Some(status) = statuses.chars().find(|status| !"#.?".contains(*status)) {
+        anyhow::bail!("Invalid spring status {:?} in {:?}", status, line);
+    }
+    let statuses: Vec<char> = statuses.chars().collect();
+    let cluster_sizes: Vec<usize> = cluster_sizes
+        .split_terminator(',')
+        .map(|nr| match nr.parse::<usize>() {
+            Ok(size) if size > 0 => Ok(size),
+            _ => anyhow::bail!("Invalid cluster size {:?} in {:?}", nr, line),
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(SpringRecord {
+        statuses,
+        cluster_sizes,
+    })
+}
+
+/// Memoized number of valid completions for every (position, group index, current run length) state of a record.
+///
+/// A state means "we have assigned everything before 'pos', fully matched the first 'group' clusters, and the last
+/// 'run' springs we assigned are damaged and count towards cluster 'group'".
+struct ArrangementTable<'a> {
+    record: &'a SpringRecord,
+    max_run: usize,
+    memo: Vec<Option<u128>>,
+}
+
+impl<'a> ArrangementTable<'a> {
+    fn new(record: &'a SpringRecord) -> Self {
+        let n_states = record.statuses.len() + 1;
+        let n_groups = record.cluster_sizes.len() + 1;
+        let max_run = record.cluster_sizes.iter().copied().max().unwrap_or(0);
+        ArrangementTable {
+            record,
+            max_run,
+            memo: vec![None; n_states * n_groups * (max_run + 1)],
+        }
+    }
+
+    fn state_idx(&self, pos: usize, group: usize, run: usize) -> usize {
+        (pos * (self.record.cluster_sizes.len() + 1) + group) * (self.max_run + 1) + run
+    }
+
+    /// The values a spring at 'pos' may take, in lexicographic order.
+    fn options(&self, pos: usize) -> &'static [char] {
+        match self.record.statuses[pos] {
+            '#' => &['#'],
+            '.' => &['.'],
+            '?' => &['#', '.'],
+            other => panic!("Invalid spring status: {}", other),
+        }
+    }
+
+    /// Returns the (group, run) state reached by assigning 'status' to the next spring, or None if that would make the
+    /// record invalid.
+    fn step(&self, group: usize, run: usize, status: char) -> Option<(usize, usize)> {
+        let clusters = &self.record.cluster_sizes;
+        if status == '#' {
+            // Extend (or start) the current damaged run.
+            (group < clusters.len() && run < clusters[group]).then_some((group, run + 1))
+        } else if run == 0 {
+            Some((group, 0))
+        } else if run == clusters[group] {
+            // An operational spring closes a run of exactly the right size.
+            Some((group + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of ways to assign the wildcards from 'pos' onwards so that the full record is valid.
+    fn completions(&mut self, pos: usize, group: usize, run: usize) -> u128 {
+        let clusters = &self.record.cluster_sizes;
+
+        if pos == self.record.statuses.len() {
+            let done = if run == 0 {
+                group == clusters.len()
+            } else {
+                group + 1 == clusters.len() && clusters[group] == run
+            };
+            return done as u128;
+        }
+
+        let idx = self.state_idx(pos, group, run);
+        if let Some(count) = self.memo[idx] {
+            return count;
+        }
+
+        let mut total = 0u128;
+        for status in self.options(pos) {
+            if let Some((next_group, next_run)) = self.step(group, run, *status) {
+                total += self.completions(pos + 1, next_group, next_run);
+            }
+        }
+
+        self.memo[idx] = Some(total);
+        total
+    }
+
+    fn total(&mut self) -> u128 {
+        self.completions(0, 0, 0)
+    }
+
+    /// Returns the arrangement with (zero-based) rank 'idx' in lexicographic order, where '#' < '.', without
+    /// enumerating the ones before it. Runs in O(len) once the table is populated.
+    fn nth_arrangement(&mut self, mut idx: u128) -> Option<Vec<char>> {
+        if idx >= self.total() {
+            return None;
+        }
+
+        let n_springs = self.record.statuses.len();
+        let mut arrangement = Vec::with_capacity(n_springs);
+        let (mut group, mut run) = (0, 0);
+        for pos in 0..n_springs {
+            for status in self.options(pos) {
+                if let Some((next_group, next_run)) = self.step(group, run, *status) {
+                    let n_below = self.completions(pos + 1, next_group, next_run);
+                    if idx < n_below {
+                        arrangement.push(*status);
+                        (group, run) = (next_group, next_run);
+                        break;
+                    }
+                    idx -= n_below;
+                }
+            }
+        }
+
+        Some(arrangement)
+    }
+}
+
+/// Lazily yields every valid arrangement of a record, in lexicographic order.
+struct Arrangements<'a> {
+    table: ArrangementTable<'a>,
+    next_idx: u128,
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let arrangement = self.table.nth_arrangement(self.next_idx)?;
+        self.next_idx += 1;
+        Some(arrangement)
+    }
+}
+
+impl SpringRecord {
+    fn arrangements(&self) -> Arrangements<'_> {
+        Arrangements {
+            table: ArrangementTable::new(self),
+            next_idx: 0,
+        }
+    }
+
+    fn nth_arrangement(&self, idx: u128) -> Option<Vec<char>> {
+        ArrangementTable::new(self).nth_arrangement(idx)
+    }
+
+    /// Draws 'k' arrangements uniformly at random (with replacement) by sampling ranks and unranking them.
+    fn sample_arrangements<R: Rng>(&self, k: usize, rng: &mut R) -> Vec<Vec<char>> {
+        let mut table = ArrangementTable::new(self);
+        let total = table.total();
+        if total == 0 {
+            return Vec::new();
+        }
+
+        (0..k)
+            .map(|_| {
+                let idx = rng.gen_range(0..total);
+                table.nth_arrangement(idx).expect("Sampled rank is in range")
+            })
+            .collect()
+    }
+}
+
+/// Counts the valid arrangements of a record in O(len * groups * max_cluster) time.
+fn count_arrangements(sr: &SpringRecord) -> u128 {
+    ArrangementTable::new(sr).total()
+}
+
+fn is_valid(statuses: &[char], cluster_sizes: &[usize]) -> bool {
+    let damaged_chunk_lens: Vec<usize> = statuses
+        .split(|c| *c == '.')
+        .map(|cluster| cluster.len())
+        .filter(|l| *l != 0)
+        .collect();
+    damaged_chunk_lens == cluster_sizes
+}
+
+/// The original brute force baseline: tries all 2^n wildcard assignments. Only usable for small records.
+fn count_arrangements_brute_force(sr: &SpringRecord) -> u128 {
+    let wild_indices: Vec<usize> = (0..sr.statuses.len()).filter(|idx| sr.statuses[*idx] == '?').collect();
+    let mut candidate = sr.statuses.clone();
+
+    (0..1u64 << wild_indices.len())
+        .filter(|variant| {
+            for (bit, idx) in wild_indices.iter().enumerate() {
+                candidate[*idx] = if (variant >> bit) & 1 == 1 { '.' } else { '#' };
+            }
+            is_valid(&candidate, &sr.cluster_sizes)
+        })
+        .count() as u128
+}
+
+/// Counts the arrangements of every record, one per line. Only covers Part One, since brute forcing the unfolded
+/// records is hopeless even for short ones.
+fn count_each(in_txt: &str, count: fn(&SpringRecord) -> u128) -> anyhow::Result<String> {
+    let counts: Vec<String> = in_txt
+        .split_terminator('\n')
+        .map(|line| parse_spring_record(line).map(|record| count(&record).to_string()))
+        .collect::<anyhow::Result<_>>()?;
+    Ok(counts.join(","))
+}
+
+/// Checks the arrangement table against the brute force on short records.
+pub fn cross_check() -> DayCheck {
+    DayCheck {
+        day: 12,
+        reference: Solver {
+            name: "brute_force",
+            solve: |in_txt| {
+                format!(
+                    "{:?}",
+                    count_each(in_txt, count_arrangements_brute_force).map_err(|e| e.to_string())
+                )
+            },
+        },
+        optimized: vec![Solver {
+            name: "table",
+            solve: |in_txt| {
+                format!(
+                    "{:?}",
+                    count_each(in_txt, count_arrangements).map_err(|e| e.to_string())
+                )
+            },
+        }],
+        random_input: |rng| {
+            let n_records = rng.gen_range(1..=4);
+            crate::gen::spring_records(rng, n_records, 12)
+        },
+    }
+}
+
+/// Counts all possible assignments of the unknown springs which are consistent with the cluster sizes, one record per
+/// line.
+fn solve_stream(reader: impl BufRead, progress_every: Option<usize>) -> anyhow::Result<(u128, u128)> {
+    let mut progress = Progress::new(progress_every);
+    let (mut part_one_answer, mut part_two_answer) = (0u128, 0u128);
+    try_for_each_line(reader, |line| {
+        let record = parse_spring_record(line)?;
+        part_one_answer += count_arrangements(&record);
+        part_two_answer += count_arrangements(&record.multiply(5));
+        progress.tick(|| (part_one_answer, part_two_answer));
+        Ok(())
+    })?;
+    Ok((part_one_answer, part_two_answer))
+}
+
+/// Parses an input without solving it, one record per line.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    try_for_each_line(in_txt.as_bytes(), |line| parse_spring_record(line).map(drop))
+}
+
+/// Times parsing the records, then counting the arrangements of each record as is and unfolded. There is no full
+/// input for this day, so it uses the demo.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 12,
+        input: "input/12-demo-01.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: in_txt
+                    .lines()
+                    .map(parse_spring_record)
+                    .collect::<anyhow::Result<Vec<SpringRecord>>>()?,
+                part_one: |records| Ok(records.iter().map(count_arrangements).sum::<u128>()),
+                part_two: Some(|records| {
+                    Ok(records
+                        .iter()
+                        .map(|record| count_arrangements(&record.multiply(5)))
+                        .sum::<u128>())
+                }),
+            }))
+        },
+    }
+}
+
+fn day_12_hot_springs(input_fpath: &Path) -> anyhow::Result<(u128, u128)> {
+    open_input(input_fpath).and_then(|reader| solve_stream(reader, None))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--stream <path|-> [--progress <n_lines>]' to solve a (possibly huge) input line by line.
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(stream) = StreamArgs::from_args(&args)? {
+        println!("{:?}", solve_stream(open_input(&stream.input)?, stream.progress_every)?);
+        return Ok(());
+    }
+
+    let example = parse_spring_record("?###???????? 3,2,1")?;
+    for arrangement in example.arrangements() {
+        println!("{}", arrangement.iter().collect::<String>());
+    }
+    for sample in example.sample_arrangements(3, &mut rand::thread_rng()) {
+        println!("Sampled: {}", sample.iter().collect::<String>());
+    }
+    if let Some(arrangement) = example.nth_arrangement(5) {
+        println!("Arrangement #5: {}", arrangement.iter().collect::<String>());
+    }
+
+    println!("{:?}", day_12_hot_springs(&PathBuf::from("input/12-demo-01.txt"))?);
+    // The full puzzle input is not checked in, so only solve it when it has been added locally.
+    let full_input = PathBuf::from("input/12.txt");
+    if full_input.exists() {
+        println!("{:?}", day_12_hot_springs(&full_input)?);
+    } else {
+        println!("Skipping the full input: {:?} is missing", full_input);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_demo_problem_12_hot_springs() {
+        assert_eq!(
+            (21, 525152),
+            day_12_hot_springs(&PathBuf::from("input/12-demo-01.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stream_demo_12_hot_springs() {
+        let lines = std::fs::read_to_string("input/12-demo-01.txt").unwrap();
+        // Reading the same records twice doubles both answers.
+        let doubled = format!("{}{}", lines, lines);
+        assert_eq!((42, 1050304), solve_stream(doubled.as_bytes(), Some(3)).unwrap());
+    }
+
+    #[test]
+    fn test_count_arrangements_matches_brute_force() {
+        let in_txt = std::fs::read_to_string("input/12-demo-01.txt").expect("Read demo input");
+        let mut records: Vec<SpringRecord> = in_txt
+            .split_terminator('\n')
+            .map(|line| parse_spring_record(line).unwrap())
+            .collect();
+        records.extend(
+            [
+                "? 1",
+                "?? 1",
+                "#?#?# 1,1,1",
+                "?#?.?? 2,1",
+                "??????? 2,1",
+                "#.#.### 1,1,3",
+                "### 2",
+                ".?? 3",
+            ]
+            .map(|line| parse_spring_record(line).unwrap()),
+        );
+
+        let doubled: Vec<SpringRecord> = records.iter().map(|r| r.multiply(2)).collect();
+
+        for sr in records.iter().chain(doubled.iter()) {
+            let expected = count_arrangements_brute_force(sr);
+            assert_eq!(
+                expected,
+                count_arrangements(sr),
+                "{:?}",
+                sr.statuses.iter().collect::<String>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_arrangements_enumeration_and_ranking() {
+        for line in [
+            "???.### 1,1,3",
+            ".??..??...?##. 1,1,3",
+            "?###???????? 3,2,1",
+            "?#?.?? 2,1",
+            "### 2",
+            ".?? 3",
+        ] {
+            let sr = parse_spring_record(line).unwrap();
+            let all: Vec<Vec<char>> = sr.arrangements().collect();
+
+            assert_eq!(count_arrangements(&sr), all.len() as u128);
+            assert!(
+                all.windows(2).all(|w| w[0] < w[1]),
+                "Not in lexicographic order: {}",
+                line
+            );
+            assert!(all.iter().all(|a| is_valid(a, &sr.cluster_sizes)));
+            for (idx, arrangement) in all.iter().enumerate() {
+                assert_eq!(Some(arrangement), sr.nth_arrangement(idx as u128).as_ref());
+            }
+            assert_eq!(None, sr.nth_arrangement(all.len() as u128));
+        }
+    }
+
+    #[test]
+    fn test_sample_arrangements() {
+        let sr = parse_spring_record("?###???????? 3,2,1").unwrap().multiply(3);
+        let mut rng = StdRng::seed_from_u64(1234);
+        let samples = sr.sample_arrangements(100, &mut rng);
+
+        assert_eq!(100, samples.len());
+        for sample in &samples {
+            assert!(is_valid(sample, &sr.cluster_sizes));
+            assert!(sample
+                .iter()
+                .zip(sr.statuses.iter())
+                .all(|(s, orig)| *orig == '?' || s == orig));
+        }
+        assert!(parse_spring_record("### 2")
+            .unwrap()
+            .sample_arrangements(5, &mut rng)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_malformed_records() {
+        assert!(parse_spring_record("???").is_err());
+        assert!(parse_spring_record("?x? 1").is_err());
+        assert!(parse_spring_record("??? 1,,1").is_err());
+        assert!(parse_spring_record("??? 0").is_err());
+        assert!(check_input("??? 1\n??? -1\n").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_spring_record_round_trips(
+            statuses in prop::collection::vec(prop::sample::select(vec!['.', '#', '?']), 0..20),
+            cluster_sizes in prop::collection::vec(1..6usize, 0..6),
+        ) {
+            let sizes: Vec<String> = cluster_sizes.iter().map(|size| size.to_string()).collect();
+            let line = format!("{} {}", statuses.iter().collect::<String>(), sizes.join(","));
+            let record = parse_spring_record(&line).unwrap();
+            prop_assert_eq!(statuses, record.statuses);
+            prop_assert_eq!(cluster_sizes, record.cluster_sizes);
+        }
+
+        #[test]
+        fn prop_known_record_has_one_arrangement_iff_valid(
+            statuses in prop::collection::vec(prop::sample::select(vec!['.', '#']), 0..20),
+        ) {
+            // The clusters of the record itself always fit, and an extra cluster never does.
+            let mut runs: Vec<String> = statuses
+                .split(|status| *status == '.')
+                .filter(|run| !run.is_empty())
+                .map(|run| run.len().to_string())
+                .collect();
+            let line = format!("{} {}", statuses.iter().collect::<String>(), runs.join(","));
+            prop_assert_eq!(1, count_arrangements(&parse_spring_record(&line).unwrap()));
+            runs.push("1".to_string());
+            let line = format!("{} {}", statuses.iter().collect::<String>(), runs.join(","));
+            prop_assert_eq!(0, count_arrangements(&parse_spring_record(&line).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! AoC 2023 Day 13: Point of Incidence
+
+use crate::bench::{DayBench, Parts};
+use anyhow::Context;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Axis {
+    /// A mirror line between two rows.
+    Horizontal,
+    /// A mirror line between two columns.
+    Vertical,
+}
+
+/// A pair of mirrored cells, as (row, col) coordinates, which differ from each other. Flipping either one fixes it.
+type Smudge = ((usize, usize), (usize, usize));
+
+#[derive(Debug, PartialEq, Eq)]
+struct Reflection {
+    axis: Axis,
+    /// Number of rows above (or columns to the left of) the mirror line.
+    offset: usize,
+    smudges: Vec<Smudge>,
+}
+
+impl Reflection {
+    fn score(&self) -> usize {
+        match self.axis {
+            Axis::Horizontal => 100 * self.offset,
+            Axis::Vertical => self.offset,
+        }
+    }
+}
+
+/// Equally long lines of bits, each stored as a bitmask split into 64-bit words, so that lines of any length work.
+struct BitLines {
+    n_words: usize,
+    words: Vec<u64>,
+}
+
+impl BitLines {
+    fn new(n_lines: usize, n_bits: usize) -> Self {
+        let n_words = n_bits.div_ceil(64);
+        BitLines {
+            n_words,
+            words: vec![0; n_lines * n_words],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.words.len() / self.n_words
+    }
+
+    fn line(&self, idx: usize) -> &[u64] {
+        &self.words[idx * self.n_words..(idx + 1) * self.n_words]
+    }
+
+    fn set(&mut self, line: usize, bit: usize) {
+        self.words[line * self.n_words + bit / 64] |= 1 << (bit % 64);
+    }
+}
+
+/// A pattern encoded as one bitmask per row and one per column, where bit 'j' of row 'i' (and bit 'i' of column 'j')
+/// is set iff cell (i, j) is a rock.
+struct Pattern {
+    rows: BitLines,
+    cols: BitLines,
+}
+
+impl Pattern {
+    fn from_chars(map: &[Vec<char>]) -> Self {
+        let n_cols = map[0].len();
+        let mut rows = BitLines::new(map.len(), n_cols);
+        let mut cols = BitLines::new(n_cols, map.len());
+        for (row_idx, row) in map.iter().enumerate() {
+            for (col_idx, ch) in row.iter().enumerate() {
+                if *ch == '#' {
+                    rows.set(row_idx, col_idx);
+                    cols.set(col_idx, row_idx);
+                }
+            }
+        }
+
+        Pattern { rows, cols }
+    }
+
+    /// Returns every mirror line along which the pattern reflects with exactly 'k' differing cell pairs.
+    ///
+    /// Part one corresponds to k = 0 and part two, with its single smudge, to k = 1.
+    fn reflections(&self, k: u32) -> Vec<Reflection> {
+        let horizontal = find_reflections(&self.rows, k)
+            .into_iter()
+            .map(|(offset, diffs)| Reflection {
+                axis: Axis::Horizontal,
+                offset,
+                smudges: diffs.into_iter().map(|(r1, r2, col)| ((r1, col), (r2, col))).collect(),
+            });
+        let vertical = find_reflections(&self.cols, k)
+            .into_iter()
+            .map(|(offset, diffs)| Reflection {
+                axis: Axis::Vertical,
+                offset,
+                smudges: diffs.into_iter().map(|(c1, c2, row)| ((row, c1), (row, c2))).collect(),
+            });
+
+        horizontal.chain(vertical).collect()
+    }
+}
+
+/// A bit which differs between a line and its mirror image, as a (line, mirrored line, bit) triplet.
+type LineDiff = (usize, usize, usize);
+
+/// Finds all mirror offsets in a list of line bitmasks whose mirrored line pairs differ by exactly 'k' bits in total.
+fn find_reflections(lines: &BitLines, k: u32) -> Vec<(usize, Vec<LineDiff>)> {
+    let mut found = Vec::new();
+    'offsets: for offset in 1..lines.len() {
+        let mut n_diffs = 0;
+        let mut diffs = Vec::new();
+        for (before, after) in (0..offset).rev().zip(offset..lines.len()) {
+            for (word_idx, (word_before, word_after)) in lines.line(before).iter().zip(lines.line(after)).enumerate() {
+                let mut diff = word_before ^ word_after;
+                n_diffs += diff.count_ones();
+                if n_diffs > k {
+                    continue 'offsets;
+                }
+                while diff != 0 {
+                    diffs.push((before, after, word_idx * 64 + diff.trailing_zeros() as usize));
+                    diff &= diff - 1;
+                }
+            }
+        }
+        if n_diffs == k {
+            found.push((offset, diffs));
+        }
+    }
+
+    found
+}
+
+/// Parses patterns separated by blank lines. Each one must be a non-empty, rectangular grid of ash ('.') and rocks
+/// ('#').
+fn parse_patterns(in_txt: &str) -> anyhow::Result<Vec<Pattern>> {
+    let lines: Vec<String> = in_txt.split_terminator('\n').map(|s| s.to_string()).collect();
+    let mut patterns = Vec::new();
+    for (map_idx, map) in crate::chunk_lines_by_blank(&lines).iter().enumerate() {
+        let map: Vec<Vec<char>> = map.iter().map(|row| row.chars().collect()).collect();
+        let n_cols = map.first().map_or(0, |row| row.len());
+        anyhow::ensure!(n_cols > 0, "Pattern {} is empty", map_idx + 1);
+        anyhow::ensure!(
+            map.iter()
+                .all(|row| row.len() == n_cols && row.iter().all(|ch| *ch == '.' || *ch == '#')),
+            "Pattern {} is not a rectangle of '.' and '#'",
+            map_idx + 1
+        );
+        patterns.push(Pattern::from_chars(&map));
+    }
+    Ok(patterns)
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_patterns(in_txt).map(drop)
+}
+
+/// Both parts are the same search, except that part two needs exactly one smudge, i.e., exactly one cell which differs
+/// from its reflection.
+fn score(patterns: &[Pattern], k: u32) -> usize {
+    patterns
+        .iter()
+        .flat_map(|p| p.reflections(k))
+        .map(|reflection| reflection.score())
+        .sum()
+}
+
+/// Times parsing the patterns, then finding their reflections without and with a smudge.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 13,
+        input: "input/13.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: parse_patterns(in_txt)?,
+                part_one: |patterns| Ok(score(patterns, 0)),
+                part_two: Some(|patterns| Ok(score(patterns, 1))),
+            }))
+        },
+    }
+}
+
+fn day_13_point_of_incidence(input_fpath: &PathBuf) -> anyhow::Result<(usize, usize)> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    let patterns = parse_patterns(&in_txt).with_context(|| format!("Parse patterns from {:?}", input_fpath))?;
+    Ok((score(&patterns, 0), score(&patterns, 1)))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    println!("{:?}", day_13_point_of_incidence(&PathBuf::from("input/13-demo.txt"))?);
+    println!("{:?}", day_13_point_of_incidence(&PathBuf::from("input/13.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_full_problem_13_point_of_incidence() {
+        assert_eq!(
+            (31265, 39359),
+            day_13_point_of_incidence(&PathBuf::from("input/13.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_demo_reflections_with_smudges() {
+        let in_txt = std::fs::read_to_string("input/13-demo.txt").expect("Read demo input");
+        let patterns = parse_patterns(&in_txt).unwrap();
+
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Vertical,
+                offset: 5,
+                smudges: vec![]
+            }],
+            patterns[0].reflections(0)
+        );
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Horizontal,
+                offset: 3,
+                smudges: vec![((0, 0), (5, 0))]
+            }],
+            patterns[0].reflections(1)
+        );
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Horizontal,
+                offset: 1,
+                smudges: vec![((0, 4), (1, 4))]
+            }],
+            patterns[1].reflections(1)
+        );
+        assert_eq!(
+            (405, 400),
+            day_13_point_of_incidence(&PathBuf::from("input/13-demo.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generated_patterns_reflect_once_per_part() {
+        let in_txt = crate::gen::generate(13, 40, 7).unwrap();
+        let lines: Vec<String> = in_txt.split_terminator('\n').map(|s| s.to_string()).collect();
+        for map in crate::chunk_lines_by_blank(&lines) {
+            let chars: Vec<Vec<char>> = map.iter().map(|row| row.chars().collect()).collect();
+            let pattern = Pattern::from_chars(&chars);
+            assert_eq!(1, pattern.reflections(0).len(), "{}", map.join("\n"));
+            assert_eq!(1, pattern.reflections(1).len(), "{}", map.join("\n"));
+        }
+    }
+
+    #[test]
+    fn test_malformed_patterns() {
+        assert!(check_input("#.\n.#\n\n##\n").is_ok());
+        // A trailing blank line makes for an empty pattern.
+        assert!(check_input("#.\n.#\n\n").is_err());
+        assert!(check_input("#.\n.\n").is_err());
+        assert!(check_input("#.\n.O\n").is_err());
+    }
+
+    #[test]
+    fn test_patterns_larger_than_a_word() {
+        // A 100x130 pattern which mirrors both between rows 50 and 51 and between columns 65 and 66.
+        let mut rng = StdRng::seed_from_u64(13);
+        let top: Vec<Vec<char>> = (0..50)
+            .map(|_| {
+                let left: Vec<char> = (0..65).map(|_| if rng.gen_bool(0.5) { '#' } else { '.' }).collect();
+                left.iter().chain(left.iter().rev()).copied().collect()
+            })
+            .collect();
+        let mut map: Vec<Vec<char>> = top.iter().chain(top.iter().rev()).cloned().collect();
+        let in_txt = crate::render(&map);
+        assert_eq!(65 + 100 * 50, score(&parse_patterns(&in_txt).unwrap(), 0));
+
+        // Flipping a corner leaves one smudge across either mirror line.
+        map[0][0] = if map[0][0] == '#' { '.' } else { '#' };
+        let pattern = Pattern::from_chars(&map);
+        assert!(pattern.reflections(0).is_empty());
+        assert_eq!(
+            vec![
+                Reflection {
+                    axis: Axis::Horizontal,
+                    offset: 50,
+                    smudges: vec![((0, 0), (99, 0))]
+                },
+                Reflection {
+                    axis: Axis::Vertical,
+                    offset: 65,
+                    smudges: vec![((0, 0), (0, 129))]
+                }
+            ],
+            pattern.reflections(1)
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! AoC 2023 Day 14 - Parabolic Reflector Dish
+
+use crate::bench::{DayBench, Parts};
+use crate::{write_ppm, Palette};
+use anyhow::Context;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// A dish of round rocks, which roll when it tilts, and cube rocks, which stay in place.
+#[derive(Clone)]
+pub struct Platform {
+    rocks: Vec<Vec<char>>,
+}
+
+/// A compact, hashable snapshot of where the round rocks are, packed row-major into 64-bit words.
+///
+/// Cube rocks never move, so the round rocks alone identify the state of a given platform.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct RockState(Vec<u64>);
+
+#[derive(Clone, Copy, Debug)]
+enum Tilt {
+    North,
+    West,
+    South,
+    East,
+}
+
+impl Tilt {
+    /// The order of the tilts in one spin cycle.
+    const CYCLE: [Tilt; 4] = [Tilt::North, Tilt::West, Tilt::South, Tilt::East];
+
+    /// The (row, col) offset a rock moves by in one animation step.
+    fn delta(&self) -> (isize, isize) {
+        match self {
+            Tilt::North => (-1, 0),
+            Tilt::West => (0, -1),
+            Tilt::South => (1, 0),
+            Tilt::East => (0, 1),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rock_grid = crate::render(&self.rocks);
+        write!(f, "Platform:\n{}", rock_grid)
+    }
+}
+
+impl Platform {
+    /// Parses a non-empty, rectangular grid of round rocks ('O'), cube rocks ('#') and empty space ('.').
+    pub fn parse(in_txt: &str) -> anyhow::Result<Self> {
+        let rocks: Vec<Vec<char>> = in_txt.split_terminator('\n').map(|row| row.chars().collect()).collect();
+        anyhow::ensure!(
+            !rocks.is_empty() && !rocks[0].is_empty(),
+            "Expected a non-empty platform"
+        );
+        for (row_idx, row) in rocks.iter().enumerate() {
+            anyhow::ensure!(
+                row.len() == rocks[0].len(),
+                "Row {} has {} cells instead of {}",
+                row_idx + 1,
+                row.len(),
+                rocks[0].len()
+            );
+            if let Some(cell) = row.iter().find(|cell| !"O#.".contains(**cell)) {
+                anyhow::bail!("Unexpected {:?} on row {}", cell, row_idx + 1);
+            }
+        }
+        Ok(Platform { rocks })
+    }
+
+    fn read(input_fpath: &Path) -> anyhow::Result<Self> {
+        let in_txt = crate::input::read_input(input_fpath)?;
+        Platform::parse(&in_txt).with_context(|| format!("Parse platform from {:?}", input_fpath))
+    }
+
+    fn n_rows(&self) -> usize {
+        self.rocks.len()
+    }
+
+    fn n_cols(&self) -> usize {
+        self.rocks[0].len()
+    }
+
+    fn load_score(&self) -> usize {
+        let n_rows = self.n_rows();
+        self.rocks
+            .iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                let factor = n_rows - row_idx;
+                let n_rocks = row.iter().filter(|ch| **ch == 'O').count();
+                factor * n_rocks
+            })
+            .sum()
+    }
+
+    fn state(&self) -> RockState {
+        let n_cols = self.n_cols();
+        let mut words = vec![0u64; (self.n_rows() * n_cols).div_ceil(64)];
+        for (row_idx, row) in self.rocks.iter().enumerate() {
+            for (col_idx, ch) in row.iter().enumerate() {
+                if *ch == 'O' {
+                    let bit = row_idx * n_cols + col_idx;
+                    words[bit / 64] |= 1 << (bit % 64);
+                }
+            }
+        }
+        RockState(words)
+    }
+
+    // Each tilt is a single pass over the grid: we keep track of the first free cell in the current segment between
+    // cube rocks for every column (or row) and move each round rock we encounter straight into it. The vertical tilts
+    // sweep whole rows at a time so they stay cache-friendly.
+
+    fn roll_up(&mut self) {
+        let mut free = vec![0; self.n_cols()];
+        for row in 0..self.n_rows() {
+            for (col, free_row) in free.iter_mut().enumerate() {
+                match self.rocks[row][col] {
+                    '#' => *free_row = row + 1,
+                    'O' => {
+                        self.rocks[row][col] = '.';
+                        self.rocks[*free_row][col] = 'O';
+                        *free_row += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn roll_down(&mut self) {
+        let n_rows = self.n_rows();
+        let mut free = vec![n_rows - 1; self.n_cols()];
+        for row in (0..n_rows).rev() {
+            for (col, free_row) in free.iter_mut().enumerate() {
+                match self.rocks[row][col] {
+                    '#' => *free_row = row.wrapping_sub(1),
+                    'O' => {
+                        self.rocks[row][col] = '.';
+                        self.rocks[*free_row][col] = 'O';
+                        *free_row = free_row.wrapping_sub(1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn roll_left(&mut self) {
+        for row in self.rocks.iter_mut() {
+            let mut free = 0;
+            for col in 0..row.len() {
+                match row[col] {
+                    '#' => free = col + 1,
+                    'O' => {
+                        row[col] = '.';
+                        row[free] = 'O';
+                        free += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn roll_right(&mut self) {
+        for row in self.rocks.iter_mut() {
+            let mut free = row.len() - 1;
+            for col in (0..row.len()).rev() {
+                match row[col] {
+                    '#' => free = col.wrapping_sub(1),
+                    'O' => {
+                        row[col] = '.';
+                        row[free] = 'O';
+                        free = free.wrapping_sub(1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Builds a bigger dish by tiling this one 'factor' times in each direction.
+    pub fn tiled(&self, factor: usize) -> Platform {
+        let wide_rows: Vec<Vec<char>> = self.rocks.iter().map(|row| row.repeat(factor)).collect();
+        Platform {
+            rocks: (0..factor).flat_map(|_| wide_rows.iter().cloned()).collect(),
+        }
+    }
+
+    /// Tilts the platform north, west, south and then east.
+    pub fn cycle(&mut self) {
+        for tilt in Tilt::CYCLE {
+            self.tilt(tilt);
+        }
+    }
+
+    /// Computes the load after 'n_cycles' spin cycles by simulating until the platform repeats a state.
+    pub fn load_after_cycles(&self, n_cycles: usize) -> usize {
+        let mut platform = self.clone();
+        let mut first_seen: HashMap<RockState, usize> = HashMap::new();
+        let mut loads: Vec<usize> = Vec::new();
+
+        for step in 0..n_cycles {
+            if let Some(loop_start) = first_seen.insert(platform.state(), step) {
+                let period = step - loop_start;
+                return loads[loop_start + (n_cycles - loop_start) % period];
+            }
+            loads.push(platform.load_score());
+            platform.cycle();
+        }
+
+        platform.load_score()
+    }
+
+    fn tilt(&mut self, tilt: Tilt) {
+        match tilt {
+            Tilt::North => self.roll_up(),
+            Tilt::West => self.roll_left(),
+            Tilt::South => self.roll_down(),
+            Tilt::East => self.roll_right(),
+        }
+    }
+
+    /// Moves every round rock which has room ahead of it by one cell towards 'tilt'. Returns whether anything moved.
+    fn nudge(&mut self, tilt: Tilt) -> bool {
+        let (d_row, d_col) = tilt.delta();
+        // Visit the cells closest to the destination edge first, so a line of rocks with room ahead moves as one.
+        let rows: Vec<usize> = if d_row > 0 {
+            (0..self.n_rows()).rev().collect()
+        } else {
+            (0..self.n_rows()).collect()
+        };
+        let cols: Vec<usize> = if d_col > 0 {
+            (0..self.n_cols()).rev().collect()
+        } else {
+            (0..self.n_cols()).collect()
+        };
+
+        let mut moved = false;
+        for row in &rows {
+            for col in &cols {
+                let target_row = row.wrapping_add_signed(d_row);
+                let target_col = col.wrapping_add_signed(d_col);
+                if self.rocks[*row][*col] == 'O'
+                    && target_row < self.n_rows()
+                    && target_col < self.n_cols()
+                    && self.rocks[target_row][target_col] == '.'
+                {
+                    self.rocks[target_row][target_col] = 'O';
+                    self.rocks[*row][*col] = '.';
+                    moved = true;
+                }
+            }
+        }
+        moved
+    }
+
+    /// Records every intermediate state of a tilt, moving the rocks one cell at a time, starting with the current one.
+    fn record_tilt(&self, tilt: Tilt) -> Recording {
+        let mut recording = Recording {
+            frames: vec![self.clone()],
+        };
+        recording.extend_with_tilt(tilt);
+        recording
+    }
+
+    /// Records every intermediate state of a full spin cycle.
+    fn record_cycle(&self) -> Recording {
+        let mut recording = Recording {
+            frames: vec![self.clone()],
+        };
+        for tilt in Tilt::CYCLE {
+            recording.extend_with_tilt(tilt);
+        }
+        recording
+    }
+}
+
+/// A sequence of platform states, e.g., to watch the rocks settle.
+struct Recording {
+    frames: Vec<Platform>,
+}
+
+impl Recording {
+    fn extend_with_tilt(&mut self, tilt: Tilt) {
+        let mut platform = self.frames.last().expect("Recordings start with one frame").clone();
+        while platform.nudge(tilt) {
+            self.frames.push(platform.clone());
+        }
+    }
+
+    /// Writes every frame as a binary PPM image called 'frame-NNNNN.ppm', with each cell drawn as a 'scale'-sized square.
+    /// Round rocks use the highlight color and cube rocks the fixed one.
+    fn write_ppm_frames(&self, out_dir: &Path, palette: &Palette, scale: usize) -> std::io::Result<()> {
+        std::fs::create_dir_all(out_dir)?;
+        for (frame_idx, frame) in self.frames.iter().enumerate() {
+            let out_fpath = out_dir.join(format!("frame-{:05}.ppm", frame_idx));
+            write_ppm(
+                &out_fpath,
+                frame.n_rows(),
+                frame.n_cols(),
+                scale,
+                |row, col| match frame.rocks[row][col] {
+                    'O' => palette.highlight,
+                    '#' => palette.fixed,
+                    _ => palette.background,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the frames as an asciicast (v2) terminal recording, which can be replayed with 'asciinema play'.
+    fn write_asciicast(&self, out_fpath: &Path, frame_delay_s: f64) -> std::io::Result<()> {
+        let (n_rows, n_cols) = self.frames.first().map(|f| (f.n_rows(), f.n_cols())).unwrap_or((0, 0));
+        let mut out = BufWriter::new(File::create(out_fpath)?);
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+            n_cols,
+            n_rows + 1
+        )?;
+        for (frame_idx, frame) in self.frames.iter().enumerate() {
+            // Move the cursor home and clear the screen before drawing each frame. The grid only contains 'O', '#' and
+            // '.', so the only characters we need to escape for JSON are the control ones we add ourselves.
+            let screen = format!(
+                "\\u001b[H\\u001b[2J{}",
+                crate::render(&frame.rocks).replace('\n', "\\r\\n")
+            );
+            writeln!(out, "[{:.3}, \"o\", \"{}\"]", frame_idx as f64 * frame_delay_s, screen)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    Platform::parse(in_txt).map(drop)
+}
+
+/// Times parsing the platform, then tilting it north once and spinning it until it loops.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 14,
+        input: "input/14.txt",
+        parse: |in_txt| {
+            Ok(Box::new(Parts {
+                input: Platform::parse(in_txt)?,
+                part_one: |platform| {
+                    let mut north_tilt_platform = platform.clone();
+                    north_tilt_platform.roll_up();
+                    Ok(north_tilt_platform.load_score())
+                },
+                part_two: Some(|platform| Ok(platform.load_after_cycles(1_000_000_000))),
+            }))
+        },
+    }
+}
+
+fn day_14_parabolic_reflector_dish(input_fpath: &Path) -> anyhow::Result<(usize, usize)> {
+    let platform = Platform::read(input_fpath)?;
+
+    let mut north_tilt_platform = platform.clone();
+    north_tilt_platform.roll_up();
+
+    let part_one_answer: usize = north_tilt_platform.load_score();
+
+    // Simulating all 1bn steps would take ages, but the platform settles into a loop quickly, so we only simulate until
+    // we see a state for the second time and then skip ahead by whole periods.
+    let part_two_answer: usize = platform.load_after_cycles(1_000_000_000);
+
+    Ok((part_one_answer, part_two_answer))
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--animate <out_dir> [north|west|south|east]' to export a recording of one spin cycle (or a single tilt) of
+    // the demo platform.
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() >= 3 && args[1] == "--animate" {
+        let out_dir = PathBuf::from(&args[2]);
+        let platform = Platform::read(Path::new("input/14-demo.txt"))?;
+        let recording = match args.get(3).map(|s| s.as_str()) {
+            None => platform.record_cycle(),
+            Some("north") => platform.record_tilt(Tilt::North),
+            Some("west") => platform.record_tilt(Tilt::West),
+            Some("south") => platform.record_tilt(Tilt::South),
+            Some("east") => platform.record_tilt(Tilt::East),
+            Some(other) => anyhow::bail!("Unknown tilt direction: {}", other),
+        };
+        let palette = Palette::from_hex("#caa173", "#5b6770", "#1d2021")?;
+        recording.write_ppm_frames(&out_dir, &palette, 16)?;
+        recording.write_asciicast(&out_dir.join("cycle.cast"), 0.1)?;
+        println!("Wrote {} frames to {:?}", recording.frames.len(), out_dir);
+        return Ok(());
+    }
+
+    println!(
+        "{:?}",
+        day_14_parabolic_reflector_dish(&PathBuf::from("input/14-demo.txt"))?
+    );
+    println!("{:?}", day_14_parabolic_reflector_dish(&PathBuf::from("input/14.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The original bubble-based north tilt, which repeats a full pass once per row.
+    fn roll_up_reference(platform: &mut Platform) {
+        for _ in 0..platform.n_rows() {
+            for row in 1..platform.n_rows() {
+                for col in 0..platform.n_cols() {
+                    if platform.rocks[row][col] == 'O' && platform.rocks[row - 1][col] == '.' {
+                        platform.rocks[row - 1][col] = 'O';
+                        platform.rocks[row][col] = '.';
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_problem_14_parabolic_reflector_dish() {
+        assert_eq!(
+            (136, 64),
+            day_14_parabolic_reflector_dish(&PathBuf::from("input/14-demo.txt")).unwrap()
+        );
+        assert_eq!(
+            (106997, 99641),
+            day_14_parabolic_reflector_dish(&PathBuf::from("input/14.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tilts_match_reference() {
+        let platform = Platform::read(Path::new("input/14.txt")).unwrap().tiled(2);
+        let mut fast = platform.clone();
+        let mut reference = platform.clone();
+        fast.roll_up();
+        roll_up_reference(&mut reference);
+        assert_eq!(reference.rocks, fast.rocks);
+
+        // Cycles must preserve the number of round rocks and leave the cube rocks in place.
+        let mut cycled = platform.clone();
+        cycled.cycle();
+        cycled.cycle();
+        let count = |p: &Platform, ch: char| p.rocks.iter().flatten().filter(|c| **c == ch).count();
+        assert_eq!(count(&platform, 'O'), count(&cycled, 'O'));
+        for (orig_row, new_row) in platform.rocks.iter().zip(cycled.rocks.iter()) {
+            for (orig, new) in orig_row.iter().zip(new_row.iter()) {
+                assert_eq!(*orig == '#', *new == '#');
+            }
+        }
+
+        // Equal platforms hash to the same state, and moving any rock changes it.
+        assert_eq!(cycled.state(), cycled.clone().state());
+        let mut tilted = cycled.clone();
+        tilted.roll_up();
+        assert_ne!(cycled.state(), tilted.state());
+    }
+
+    #[test]
+    fn test_recordings_end_in_the_settled_state() {
+        let platform = Platform::read(Path::new("input/14-demo.txt")).unwrap();
+        for tilt in Tilt::CYCLE {
+            let recording = platform.record_tilt(tilt);
+            let mut settled = platform.clone();
+            settled.tilt(tilt);
+            assert_eq!(platform.rocks, recording.frames[0].rocks);
+            assert_eq!(settled.rocks, recording.frames.last().unwrap().rocks);
+        }
+
+        let recording = platform.record_cycle();
+        let mut cycled = platform.clone();
+        cycled.cycle();
+        assert_eq!(cycled.rocks, recording.frames.last().unwrap().rocks);
+
+        let out_dir = std::env::temp_dir().join("aoc23-14-recording-test");
+        let palette = Palette::from_hex("#caa173", "#5b6770", "#1d2021").unwrap();
+        recording.write_ppm_frames(&out_dir, &palette, 2).unwrap();
+        let first_frame = std::fs::read(out_dir.join("frame-00000.ppm")).unwrap();
+        let header = b"P6\n20 20\n255\n";
+        assert_eq!(header.len() + 20 * 20 * 3, first_frame.len());
+        assert!(first_frame.starts_with(header));
+
+        recording.write_asciicast(&out_dir.join("cycle.cast"), 0.1).unwrap();
+        let cast = std::fs::read_to_string(out_dir.join("cycle.cast")).unwrap();
+        assert_eq!(recording.frames.len() + 1, cast.lines().count());
+    }
+}
@@ -0,0 +1,671 @@
+//! AoC 2023 Day 15: Lens Library
+
+use crate::bench::{DayBench, Parts};
+use crate::OrderedMap;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Add { label: String, focal_length: i32 },
+    Remove { label: String },
+}
+
+impl Op {
+    fn label(&self) -> &str {
+        match self {
+            Op::Add { label, .. } => label,
+            Op::Remove { label } => label,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Box {
+    lenses: OrderedMap<String, i32>,
+}
+
+impl Box {
+    fn new() -> Self {
+        Box {
+            lenses: OrderedMap::new(),
+        }
+    }
+
+    /// Returns whether a lens was actually removed.
+    fn remove_by(&mut self, key: &str) -> bool {
+        self.lenses.remove(key).is_some()
+    }
+
+    /// Returns whether an existing lens was replaced, as opposed to a new one being added at the back.
+    fn update_by(&mut self, key: &str, focal_length: i32) -> bool {
+        match self.lenses.get_mut(key) {
+            Some(old_focal_length) => {
+                *old_focal_length = focal_length;
+                true
+            }
+            None => {
+                self.lenses.insert(key.to_string(), focal_length);
+                false
+            }
+        }
+    }
+
+    fn focusing_score(&self) -> usize {
+        self.lenses
+            .values()
+            .enumerate()
+            .map(|(idx, focal_length)| (idx + 1) * (*focal_length as usize))
+            .sum()
+    }
+}
+
+fn parse_op(input: &str) -> anyhow::Result<Op> {
+    if let Some(label) = input.strip_suffix('-') {
+        Ok(Op::Remove {
+            label: label.to_string(),
+        })
+    } else {
+        let (label, rhs) = input
+            .split_once('=')
+            .with_context(|| format!("Expected '<label>-' or '<label>=<focal length>', got {:?}", input))?;
+        let focal_length: i32 = match rhs.parse() {
+            Ok(focal_length) if (1..=9).contains(&focal_length) => focal_length,
+            _ => anyhow::bail!("Expected a focal length from 1 to 9 in {:?}", input),
+        };
+        Ok(Op::Add {
+            label: label.to_string(),
+            focal_length,
+        })
+    }
+}
+
+/// A hash function which assigns lens labels to one of a fixed number of boxes.
+trait LensHash {
+    fn n_buckets(&self) -> usize;
+
+    /// Must return a value in [0, n_buckets).
+    fn hash(&self, label: &str) -> usize;
+}
+
+/// The reindeer hash from the problem statement, with its 256 boxes.
+struct ReindeerHash;
+
+impl LensHash for ReindeerHash {
+    fn n_buckets(&self) -> usize {
+        256
+    }
+
+    fn hash(&self, label: &str) -> usize {
+        reindeer_hash(label)
+    }
+}
+
+/// The reindeer recipe with a configurable multiplier and bucket count, i.e., a family of multiplicative hashes.
+struct MultiplicativeHash {
+    multiplier: usize,
+    n_buckets: usize,
+}
+
+impl MultiplicativeHash {
+    fn new(multiplier: usize, n_buckets: usize) -> anyhow::Result<Self> {
+        anyhow::ensure!(n_buckets > 0, "A hash needs at least one bucket");
+        Ok(MultiplicativeHash { multiplier, n_buckets })
+    }
+}
+
+impl LensHash for MultiplicativeHash {
+    fn n_buckets(&self) -> usize {
+        self.n_buckets
+    }
+
+    fn hash(&self, label: &str) -> usize {
+        label.bytes().fold(0, |cur, byte| {
+            ((cur + byte as usize) * self.multiplier) % self.n_buckets
+        })
+    }
+}
+
+/// 64-bit FNV-1a, reduced modulo the bucket count.
+struct Fnv1aHash {
+    n_buckets: usize,
+}
+
+impl Fnv1aHash {
+    fn new(n_buckets: usize) -> anyhow::Result<Self> {
+        anyhow::ensure!(n_buckets > 0, "A hash needs at least one bucket");
+        Ok(Fnv1aHash { n_buckets })
+    }
+}
+
+impl LensHash for Fnv1aHash {
+    fn n_buckets(&self) -> usize {
+        self.n_buckets
+    }
+
+    fn hash(&self, label: &str) -> usize {
+        let hash = label.bytes().fold(0xcbf29ce484222325u64, |cur, byte| {
+            (cur ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        (hash % self.n_buckets as u64) as usize
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OpOutcome {
+    Added,
+    Replaced,
+    Removed,
+    RemovedMissing,
+}
+
+/// What a single operation did, for inspecting how the library evolves.
+#[derive(Debug)]
+struct OpRecord {
+    label: String,
+    bucket: usize,
+    outcome: OpOutcome,
+    /// Number of lenses in the bucket after the operation.
+    chain_len: usize,
+}
+
+#[derive(Debug)]
+struct LibraryStats {
+    n_lenses: usize,
+    n_buckets: usize,
+    n_occupied_buckets: usize,
+    /// Number of buckets which hold more than one lens.
+    n_collision_chains: usize,
+    max_chain_len: usize,
+    /// Average number of lenses in the occupied buckets.
+    mean_chain_len: f64,
+    /// 'occupancy_histogram[k]' is the number of buckets holding exactly 'k' lenses.
+    occupancy_histogram: Vec<usize>,
+}
+
+/// The contents of every non-empty box after the first 'step' operations, as (label, focal length) lists.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Snapshot {
+    step: usize,
+    boxes: BTreeMap<usize, Vec<(String, i32)>>,
+}
+
+impl Snapshot {
+    /// Returns the boxes whose contents differ between two snapshots, as (box, lenses here, lenses in 'other').
+    fn diff(&self, other: &Snapshot) -> Vec<BoxDiff> {
+        let box_ids: Vec<usize> = self.boxes.keys().chain(other.boxes.keys()).copied().collect();
+        let mut diffs: Vec<BoxDiff> = Vec::new();
+        for box_id in box_ids.into_iter().collect::<std::collections::BTreeSet<usize>>() {
+            let here = self.boxes.get(&box_id).cloned().unwrap_or_default();
+            let there = other.boxes.get(&box_id).cloned().unwrap_or_default();
+            if here != there {
+                diffs.push((box_id, here, there));
+            }
+        }
+        diffs
+    }
+}
+
+type BoxDiff = (usize, Vec<(String, i32)>, Vec<(String, i32)>);
+
+/// A HASHMAP simulator: a row of boxes, a hash function assigning labels to them and the history of operations.
+///
+/// The library keeps a log of every operation it was given, so it can be rewound to any earlier step and replayed
+/// forward again. Applying a new operation after rewinding drops the operations which were undone.
+struct LensLibrary<H: LensHash = ReindeerHash> {
+    hasher: H,
+    boxes: Vec<Box>,
+    /// One record per applied operation.
+    history: Vec<OpRecord>,
+    log: Vec<Op>,
+}
+
+impl<H: LensHash> LensLibrary<H> {
+    fn new(hasher: H) -> Self {
+        let boxes = (0..hasher.n_buckets()).map(|_| Box::new()).collect();
+        LensLibrary {
+            hasher,
+            boxes,
+            history: Vec::new(),
+            log: Vec::new(),
+        }
+    }
+
+    /// The number of operations currently applied.
+    fn step(&self) -> usize {
+        self.history.len()
+    }
+
+    fn apply(&mut self, op: &Op) {
+        self.log.truncate(self.step());
+        self.log.push(op.clone());
+        self.apply_logged();
+    }
+
+    /// Applies the first logged operation which has not been applied yet.
+    fn apply_logged(&mut self) {
+        let op = &self.log[self.step()];
+        let bucket = self.hasher.hash(op.label());
+        let outcome = match op {
+            Op::Add { label, focal_length } => match self.boxes[bucket].update_by(label, *focal_length) {
+                true => OpOutcome::Replaced,
+                false => OpOutcome::Added,
+            },
+            Op::Remove { label } => match self.boxes[bucket].remove_by(label) {
+                true => OpOutcome::Removed,
+                false => OpOutcome::RemovedMissing,
+            },
+        };
+
+        self.history.push(OpRecord {
+            label: op.label().to_string(),
+            bucket,
+            outcome,
+            chain_len: self.boxes[bucket].lenses.len(),
+        });
+    }
+
+    /// Rewinds or fast-forwards the library to the state right after the first 'step' logged operations.
+    ///
+    /// Rewinding replays the log from an empty library, since removals do not remember where a lens used to be.
+    fn replay_to(&mut self, step: usize) {
+        assert!(
+            step <= self.log.len(),
+            "Cannot replay to step {step} of {}",
+            self.log.len()
+        );
+        if step < self.step() {
+            self.boxes.iter_mut().for_each(|b| b.lenses.clear());
+            self.history.clear();
+        }
+        while self.step() < step {
+            self.apply_logged();
+        }
+    }
+
+    fn undo(&mut self, n_ops: usize) {
+        self.replay_to(self.step().saturating_sub(n_ops));
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let boxes = self
+            .boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| !b.lenses.is_empty())
+            .map(|(box_id, b)| (box_id, b.lenses.iter().map(|(l, f)| (l.clone(), *f)).collect()))
+            .collect();
+        Snapshot {
+            step: self.step(),
+            boxes,
+        }
+    }
+
+    /// Returns the first step whose state differs from the reference snapshot for that step, or None if the whole log
+    /// agrees with the reference. The operation responsible is the one at index 'step - 1' in the log.
+    ///
+    /// Uses binary search over the steps, so it assumes that once the states diverge they never agree again. The
+    /// reference must hold one snapshot per step, starting with step 0.
+    fn find_first_divergence(&mut self, reference: &[Snapshot]) -> Option<usize> {
+        let n_steps = self.log.len().min(reference.len().saturating_sub(1));
+        let diverges_at = |library: &mut Self, step: usize| {
+            library.replay_to(step);
+            library.snapshot() != reference[step]
+        };
+
+        if !diverges_at(self, n_steps) {
+            return None;
+        }
+        let (mut lo, mut hi) = (0, n_steps);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if diverges_at(self, mid) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        Some(lo)
+    }
+
+    fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .map(|(box_number, b)| (1 + box_number) * b.focusing_score())
+            .sum()
+    }
+
+    fn stats(&self) -> LibraryStats {
+        let chain_lens: Vec<usize> = self.boxes.iter().map(|b| b.lenses.len()).collect();
+        let n_lenses: usize = chain_lens.iter().sum();
+        let max_chain_len = chain_lens.iter().copied().max().unwrap_or(0);
+        let n_occupied_buckets = chain_lens.iter().filter(|len| **len > 0).count();
+
+        let mut occupancy_histogram = vec![0; max_chain_len + 1];
+        for len in &chain_lens {
+            occupancy_histogram[*len] += 1;
+        }
+
+        LibraryStats {
+            n_lenses,
+            n_buckets: self.boxes.len(),
+            n_occupied_buckets,
+            n_collision_chains: chain_lens.iter().filter(|len| **len > 1).count(),
+            max_chain_len,
+            mean_chain_len: n_lenses as f64 / n_occupied_buckets.max(1) as f64,
+            occupancy_histogram,
+        }
+    }
+}
+
+fn process_ops<H: LensHash>(ops: &[Op], hasher: H) -> LensLibrary<H> {
+    let mut library = LensLibrary::new(hasher);
+    for op in ops {
+        library.apply(op);
+    }
+
+    library
+}
+
+/// Implements the reindeer hash from the problem statement
+///
+/// Determine the ASCII code for the current character of the string.
+/// Increase the current value by the ASCII code you just determined.
+/// Set the current value to itself multiplied by 17.
+/// Set the current value to the remainder of dividing itself by 256.
+fn reindeer_hash(input: &str) -> usize {
+    input.chars().fold(0, |cur, ch| ((cur + (ch as usize)) * 17) % 256)
+}
+
+fn parse_ops(in_txt: &str) -> anyhow::Result<Vec<Op>> {
+    // Newlines are not part of the initialization sequence.
+    in_txt
+        .replace('\n', "")
+        .split_terminator(',')
+        .enumerate()
+        .map(|(step_idx, step)| parse_op(step).with_context(|| format!("Step {}", step_idx + 1)))
+        .collect()
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_ops(in_txt).map(drop)
+}
+
+fn read_ops(input_fpath: &PathBuf) -> anyhow::Result<Vec<Op>> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    parse_ops(&in_txt).with_context(|| format!("Parse steps from {:?}", input_fpath))
+}
+
+/// Times splitting the sequence into steps and parsing them, then hashing the steps and arranging the lenses.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 15,
+        input: "input/15.txt",
+        parse: |in_txt| {
+            // Newlines are not part of the initialization sequence.
+            let sequence = in_txt.replace('\n', "");
+            let ops = parse_ops(&sequence)?;
+            let codes: Vec<String> = sequence.split_terminator(',').map(String::from).collect();
+            Ok(std::boxed::Box::new(Parts {
+                input: (codes, ops),
+                part_one: |(codes, _)| Ok(codes.iter().map(|code| reindeer_hash(code)).sum::<usize>()),
+                part_two: Some(|(_, ops)| Ok(process_ops(ops, ReindeerHash).focusing_power())),
+            }))
+        },
+    }
+}
+
+fn day_15_lens_library(input_fpath: &PathBuf) -> anyhow::Result<(usize, usize)> {
+    // Newlines are not part of the initialization sequence.
+    let in_txt = crate::input::read_input(input_fpath)?.replace('\n', "");
+    let codes: Vec<&str> = in_txt.split_terminator(',').collect();
+    let hashes: Vec<usize> = codes.clone().into_iter().map(reindeer_hash).collect();
+    let part_one_answer: usize = hashes.iter().sum();
+
+    let ops: Vec<Op> = parse_ops(&in_txt).with_context(|| format!("Parse steps from {:?}", input_fpath))?;
+    let part_two_answer: usize = process_ops(&ops, ReindeerHash).focusing_power();
+
+    Ok((part_one_answer, part_two_answer))
+}
+
+/// Prints how the lenses of an input spread out over the boxes under a given hash function.
+fn print_hash_experiment<H: LensHash>(name: &str, ops: &[Op], hasher: H) {
+    let library = process_ops(ops, hasher);
+    let stats = library.stats();
+    println!(
+        "{name}: {} lenses in {}/{} boxes, {} collision chains, max chain {}, mean chain {:.2}, histogram {:?}",
+        stats.n_lenses,
+        stats.n_occupied_buckets,
+        stats.n_buckets,
+        stats.n_collision_chains,
+        stats.max_chain_len,
+        stats.mean_chain_len,
+        stats.occupancy_histogram
+    );
+
+    let n_misses = library
+        .history
+        .iter()
+        .filter(|record| record.outcome == OpOutcome::RemovedMissing)
+        .count();
+    if let Some(longest) = library.history.iter().max_by_key(|record| record.chain_len) {
+        println!(
+            "{name}: {n_misses} removals of missing lenses, longest chain was {} in box {} after an op on '{}'",
+            longest.chain_len, longest.bucket, longest.label
+        );
+    }
+}
+
+/// Writes one JSON snapshot per line for every step of the given operations, starting with the empty library.
+fn dump_snapshots(ops: &[Op], out_fpath: &PathBuf) -> anyhow::Result<()> {
+    let mut out = BufWriter::new(std::fs::File::create(out_fpath)?);
+    let mut library = LensLibrary::new(ReindeerHash);
+    writeln!(out, "{}", serde_json::to_string(&library.snapshot())?)?;
+    for op in ops {
+        library.apply(op);
+        writeln!(out, "{}", serde_json::to_string(&library.snapshot())?)?;
+    }
+    Ok(())
+}
+
+fn read_snapshots(in_fpath: &PathBuf) -> anyhow::Result<Vec<Snapshot>> {
+    let reader = BufReader::new(std::fs::File::open(in_fpath)?);
+    let mut snapshots = Vec::new();
+    for line in reader.lines() {
+        snapshots.push(serde_json::from_str(&line?)?);
+    }
+    Ok(snapshots)
+}
+
+/// Finds and explains the first operation whose outcome differs from a reference snapshot log.
+fn bisect(ops: &[Op], reference_fpath: &PathBuf) -> anyhow::Result<()> {
+    let reference = read_snapshots(reference_fpath)?;
+    let library = &mut process_ops(ops, ReindeerHash);
+    let Some(step) = library.find_first_divergence(&reference) else {
+        println!(
+            "No divergence from the reference in {} steps.",
+            reference.len().saturating_sub(1)
+        );
+        return Ok(());
+    };
+
+    if step == 0 {
+        println!("The reference does not start from an empty library.");
+        return Ok(());
+    }
+    library.replay_to(step);
+    let after = library.snapshot();
+    library.undo(1);
+    let before = library.snapshot();
+    println!(
+        "First divergence after step {step}, caused by op #{}: {:?}",
+        step - 1,
+        ops[step - 1]
+    );
+    println!("The op changed: {:?}", before.diff(&after));
+    println!("Ours vs. reference: {:?}", after.diff(&reference[step]));
+    Ok(())
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--dump-snapshots <out.jsonl>' to record the state after every step of the full input, and
+    // '--bisect <reference.jsonl>' to find the first operation where we disagree with such a recording.
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() == 3 {
+        let ops = read_ops(&PathBuf::from("input/15.txt"))?;
+        let fpath = PathBuf::from(&args[2]);
+        return match args[1].as_str() {
+            "--dump-snapshots" => dump_snapshots(&ops, &fpath),
+            "--bisect" => bisect(&ops, &fpath),
+            other => anyhow::bail!("Unknown option: {}", other),
+        };
+    }
+
+    println!("{:?}", day_15_lens_library(&PathBuf::from("input/15-demo.txt"))?);
+    println!("{:?}", day_15_lens_library(&PathBuf::from("input/15.txt"))?);
+
+    let ops = read_ops(&PathBuf::from("input/15.txt"))?;
+    print_hash_experiment("reindeer", &ops, ReindeerHash);
+    print_hash_experiment("x31 mod 256", &ops, MultiplicativeHash::new(31, 256)?);
+    print_hash_experiment("fnv1a mod 256", &ops, Fnv1aHash::new(256)?);
+    print_hash_experiment("fnv1a mod 64", &ops, Fnv1aHash::new(64)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_full_problem_15_lens_library() {
+        assert_eq!(
+            (1320, 145),
+            day_15_lens_library(&PathBuf::from("input/15-demo.txt")).unwrap()
+        );
+        assert_eq!(
+            (520500, 213097),
+            day_15_lens_library(&PathBuf::from("input/15.txt")).unwrap()
+        );
+
+        // A trailing newline (or a Windows one) is not part of the last step.
+        let fpath = std::env::temp_dir().join("aoc23-15-newline.txt");
+        std::fs::write(&fpath, "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,\r\nab=5,pc-,pc=6,ot=7\r\n").unwrap();
+        assert_eq!((1320, 145), day_15_lens_library(&fpath).unwrap());
+    }
+
+    #[test]
+    fn test_lens_library_stats_and_history() {
+        let ops = read_ops(&PathBuf::from("input/15-demo.txt")).unwrap();
+
+        // The generalized multiplicative hash with the puzzle's parameters is the reindeer hash.
+        let reindeer_like = MultiplicativeHash::new(17, 256).unwrap();
+        assert_eq!(145, process_ops(&ops, reindeer_like).focusing_power());
+
+        let library = process_ops(&ops, ReindeerHash);
+        let stats = library.stats();
+        // Box 0 ends with "rn 1" and "cm 2", box 3 with "ot 7", "ab 5" and "pc 6".
+        assert_eq!(5, stats.n_lenses);
+        assert_eq!(2, stats.n_occupied_buckets);
+        assert_eq!(2, stats.n_collision_chains);
+        assert_eq!(3, stats.max_chain_len);
+        assert_eq!(vec![254, 0, 1, 1], stats.occupancy_histogram);
+
+        assert_eq!(ops.len(), library.history.len());
+        assert_eq!(OpOutcome::RemovedMissing, library.history[1].outcome);
+        assert_eq!(OpOutcome::Added, library.history[3].outcome);
+        assert_eq!(OpOutcome::Replaced, library.history[10].outcome);
+        assert_eq!("qp", library.history[4].label);
+        assert_eq!(1, library.history[4].bucket);
+        assert_eq!(0, library.history[4].chain_len);
+
+        // Any hash family gives a consistent library, just with a different layout.
+        let fnv = process_ops(&ops, Fnv1aHash::new(7).unwrap());
+        assert_eq!(5, fnv.stats().n_lenses);
+        assert!(fnv.history.iter().all(|record| record.bucket < 7));
+
+        // Without any buckets there would be nowhere to put a lens.
+        assert!(MultiplicativeHash::new(17, 0).is_err());
+        assert!(Fnv1aHash::new(0).is_err());
+    }
+
+    /// Snapshots of every step of a run, starting with the empty library.
+    fn snapshots_per_step(ops: &[Op]) -> Vec<Snapshot> {
+        let mut library = LensLibrary::new(ReindeerHash);
+        let mut snapshots = vec![library.snapshot()];
+        for op in ops {
+            library.apply(op);
+            snapshots.push(library.snapshot());
+        }
+        snapshots
+    }
+
+    #[test]
+    fn test_replay_undo_and_snapshots() {
+        let ops = read_ops(&PathBuf::from("input/15-demo.txt")).unwrap();
+        let snapshots = snapshots_per_step(&ops);
+        let mut library = process_ops(&ops, ReindeerHash);
+        assert_eq!(145, library.focusing_power());
+
+        library.undo(3);
+        assert_eq!(snapshots[ops.len() - 3], library.snapshot());
+        library.replay_to(2);
+        assert_eq!(snapshots[2], library.snapshot());
+        library.replay_to(ops.len());
+        assert_eq!(snapshots[ops.len()], library.snapshot());
+        assert_eq!(145, library.focusing_power());
+
+        // Applying an op after undoing drops the undone part of the log.
+        library.undo(1);
+        library.apply(&parse_op("zz=1").unwrap());
+        assert_eq!(ops.len(), library.step());
+        assert_eq!(ops.len(), library.log.len());
+
+        // Step 5 leaves "rn 1" and "cm 2" in box 0; step 6 puts "pc 4" in box 3.
+        assert_eq!(
+            vec![(3, vec![], vec![("pc".to_string(), 4)])],
+            snapshots[5].diff(&snapshots[6])
+        );
+
+        let json = serde_json::to_string(&snapshots[ops.len()]).unwrap();
+        assert_eq!(snapshots[ops.len()], serde_json::from_str::<Snapshot>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_find_first_divergence() {
+        let ops = read_ops(&PathBuf::from("input/15-demo.txt")).unwrap();
+        let mut library = process_ops(&ops, ReindeerHash);
+        assert_eq!(None, library.find_first_divergence(&snapshots_per_step(&ops)));
+
+        // A reference which disagrees on the focal length set by op #7 ("ab=5") diverges right after that op.
+        let mut buggy_ops = ops.clone();
+        buggy_ops[7] = parse_op("ab=9").unwrap();
+        assert_eq!(Some(8), library.find_first_divergence(&snapshots_per_step(&buggy_ops)));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_op_round_trips(label in "[a-z]{1,8}", focal_length in 1..10i32, remove: bool) {
+            if remove {
+                let op = parse_op(&format!("{}-", label)).unwrap();
+                prop_assert!(matches!(op, Op::Remove { .. }), "{:?}", op);
+                prop_assert_eq!(label.as_str(), op.label());
+            } else {
+                let op = parse_op(&format!("{}={}", label, focal_length)).unwrap();
+                prop_assert!(matches!(op, Op::Add { focal_length: f, .. } if f == focal_length), "{:?}", op);
+                prop_assert_eq!(label.as_str(), op.label());
+            }
+        }
+
+        #[test]
+        fn prop_parse_op_rejects_bad_focal_lengths(label in "[a-z]{1,8}", rhs in "-?[0-9]{1,3}|[a-z=]{0,3}") {
+            let in_range = matches!(rhs.parse::<i32>(), Ok(focal_length) if (1..=9).contains(&focal_length));
+            prop_assert_eq!(in_range, parse_op(&format!("{}={}", label, rhs)).is_ok());
+        }
+    }
+}
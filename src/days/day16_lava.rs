@@ -0,0 +1,882 @@
+//! AoC 2023 Day 16: The Floor Will Be Lava
+
+use crate::bench::{DayBench, Parts};
+use crate::check::{DayCheck, Solver};
+use crate::{write_ppm, Palette};
+use anyhow::Context;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dir {
+    Up = 0,
+    Down = 1,
+    Left = 2,
+    Right = 3,
+}
+
+unsafe impl Send for Dir {}
+unsafe impl Sync for Dir {}
+
+impl Dir {
+    const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
+
+    fn mask(self) -> u8 {
+        1 << (self as u8)
+    }
+
+    fn parse(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "up" => Ok(Dir::Up),
+            "down" => Ok(Dir::Down),
+            "left" => Ok(Dir::Left),
+            "right" => Ok(Dir::Right),
+            other => anyhow::bail!("Unknown direction: {:?}", other),
+        }
+    }
+}
+
+/// The rules of the puzzle, written in the optics config format.
+const STANDARD_OPTICS: &str = r"
+; Empty space
+. * -> straight
+; Vertical splitter
+| up -> up
+| down -> down
+| left -> up down
+| right -> up down
+; Horizontal splitter
+- left -> left
+- right -> right
+- up -> left right
+- down -> left right
+; Right-leaning mirror
+\ up -> left
+\ down -> right
+\ left -> up
+\ right -> down
+; Left-leaning mirror
+/ up -> right
+/ down -> left
+/ left -> down
+/ right -> up
+";
+
+/// How each kind of tile redirects, splits or absorbs a beam, declared as data.
+///
+/// The config has one rule per line, '<tile> <incoming> -> <outgoing...>', where directions are 'up', 'down', 'left' or
+/// 'right'. The incoming direction may be '*' to match any direction, and the outgoing direction 'straight' keeps the
+/// incoming one. A rule with no outgoing directions absorbs the beam, as does any direction which a declared tile has
+/// no rule for. Lines starting with ';' are comments.
+#[derive(Clone, Debug)]
+struct Optics {
+    /// Outgoing directions (as a 'Dir::mask' union) for each ASCII tile and incoming direction, if the tile is known.
+    rules: Vec<Option<[u8; 4]>>,
+}
+
+impl Optics {
+    fn standard() -> Self {
+        Optics::parse(STANDARD_OPTICS).expect("The standard optics config is valid")
+    }
+
+    fn from_file(config_fpath: &Path) -> anyhow::Result<Self> {
+        let config = std::fs::read_to_string(config_fpath)
+            .with_context(|| format!("Read optics config from {:?}", config_fpath))?;
+        Optics::parse(&config)
+    }
+
+    fn parse(config: &str) -> anyhow::Result<Self> {
+        let mut rules: Vec<Option<[u8; 4]>> = vec![None; 128];
+        for (line_idx, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            Optics::parse_rule(&mut rules, line).with_context(|| format!("Optics rule on line {}", line_idx + 1))?;
+        }
+        Ok(Optics { rules })
+    }
+
+    fn parse_rule(rules: &mut [Option<[u8; 4]>], line: &str) -> anyhow::Result<()> {
+        let (lhs, rhs) = line.split_once("->").context("Missing '->'")?;
+        let lhs: Vec<&str> = lhs.split_whitespace().collect();
+        let [tile, incoming] = lhs[..] else {
+            anyhow::bail!("Expected '<tile> <incoming>' before '->', got {:?}", lhs);
+        };
+        let mut tile_chars = tile.chars();
+        let tile = match (tile_chars.next(), tile_chars.next()) {
+            (Some(c), None) if c.is_ascii_graphic() => c,
+            _ => anyhow::bail!("Tiles must be a single printable ASCII character, got {:?}", tile),
+        };
+        let incoming_dirs = match incoming {
+            "*" => Dir::ALL.to_vec(),
+            name => vec![Dir::parse(name)?],
+        };
+        let outgoing: Vec<&str> = rhs.split_whitespace().collect();
+
+        let tile_rules = rules[tile as usize].get_or_insert([0; 4]);
+        for in_dir in incoming_dirs {
+            let mut out_mask = 0;
+            for name in &outgoing {
+                out_mask |= match *name {
+                    "straight" => in_dir.mask(),
+                    name => Dir::parse(name)?.mask(),
+                };
+            }
+            tile_rules[in_dir as usize] = out_mask;
+        }
+        Ok(())
+    }
+
+    fn knows(&self, tile: char) -> bool {
+        matches!(self.rules.get(tile as usize), Some(Some(_)))
+    }
+
+    /// Returns the outgoing directions of a beam entering 'tile' in direction 'dir', as a 'Dir::mask' union. Tiles
+    /// without rules absorb the beam, but 'parse_contraption' rejects them before they get here.
+    fn outgoing(&self, tile: char, dir: Dir) -> u8 {
+        self.rules
+            .get(tile as usize)
+            .copied()
+            .flatten()
+            .map_or(0, |tile_rules| tile_rules[dir as usize])
+    }
+}
+
+/// Returns zero or more coordinates where the beam currently at state 'cmd' will have arrived after one step.
+fn handle_cmd(map: &[Vec<char>], optics: &Optics, cmd: &(usize, usize, Dir)) -> Vec<(usize, usize, Dir)> {
+    let (c_row, c_col, c_dir) = *cmd;
+    let n_rows = map.len();
+    let n_cols = map[0].len();
+    let out_mask = optics.outgoing(map[c_row][c_col], c_dir);
+
+    Dir::ALL
+        .iter()
+        .filter(|dir| out_mask & dir.mask() != 0)
+        .filter_map(|dir| {
+            let (r, c) = match dir {
+                Dir::Up => (c_row.checked_sub(1)?, c_col),
+                Dir::Down => (c_row + 1, c_col),
+                Dir::Left => (c_row, c_col.checked_sub(1)?),
+                Dir::Right => (c_row, c_col + 1),
+            };
+            (r < n_rows && c < n_cols).then_some((r, c, *dir))
+        })
+        .collect()
+}
+
+/// The directions of all beams passing through each tile, as a bitmask of 'Dir::mask' values.
+struct BeamMap {
+    beams: Vec<Vec<u8>>,
+}
+
+impl BeamMap {
+    fn is_energized(&self, row: usize, col: usize) -> bool {
+        self.beams[row][col] != 0
+    }
+
+    fn energy(&self) -> usize {
+        self.beams.iter().flatten().filter(|mask| **mask != 0).count()
+    }
+
+    /// Draws a tile the way the puzzle statement does: optics stay as they are, empty tiles crossed by one beam get an
+    /// arrow, and empty tiles crossed by several beams get the number of directions.
+    fn glyph(&self, contraption: &[Vec<char>], row: usize, col: usize) -> char {
+        let mask = self.beams[row][col];
+        match (contraption[row][col], mask.count_ones()) {
+            ('.', 0) => '.',
+            ('.', 1) if mask == Dir::Up.mask() => '^',
+            ('.', 1) if mask == Dir::Down.mask() => 'v',
+            ('.', 1) if mask == Dir::Left.mask() => '<',
+            ('.', 1) => '>',
+            ('.', n) => char::from_digit(n, 10).expect("At most four directions"),
+            (optic, _) => optic,
+        }
+    }
+
+    fn draw(&self, contraption: &[Vec<char>]) -> Vec<Vec<char>> {
+        (0..contraption.len())
+            .map(|row| {
+                (0..contraption[row].len())
+                    .map(|col| self.glyph(contraption, row, col))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Renders the beams for a terminal, coloring energized tiles with 24-bit ANSI escapes.
+    fn render_ansi(&self, contraption: &[Vec<char>], palette: &Palette) -> String {
+        let (r, g, b) = palette.highlight;
+        let mut out = String::new();
+        for (row_idx, row) in self.draw(contraption).iter().enumerate() {
+            for (col_idx, glyph) in row.iter().enumerate() {
+                if self.is_energized(row_idx, col_idx) {
+                    out.push_str(&format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, glyph));
+                } else {
+                    out.push(*glyph);
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Writes the energized tiles as a binary PPM (P6) image, with 'scale' x 'scale' pixels per tile.
+    fn write_ppm(
+        &self,
+        out_fpath: &Path,
+        contraption: &[Vec<char>],
+        palette: &Palette,
+        scale: usize,
+    ) -> std::io::Result<()> {
+        write_ppm(out_fpath, contraption.len(), contraption[0].len(), scale, |row, col| {
+            if contraption[row][col] != '.' {
+                palette.fixed
+            } else if self.is_energized(row, col) {
+                palette.highlight
+            } else {
+                palette.background
+            }
+        })
+    }
+}
+
+/// Traces the initial beam of light defined by the 'start_' parameters throughout 'in_contraption', recording the
+/// direction of every beam crossing each tile.
+fn trace_beams(
+    in_contraption: &[Vec<char>],
+    optics: &Optics,
+    start_row: usize,
+    start_col: usize,
+    start_dir: Dir,
+) -> BeamMap {
+    let n_cols = in_contraption[0].len();
+    let mut beams: Vec<Vec<u8>> = vec![vec![0; n_cols]; in_contraption.len()];
+
+    let mut queue: Vec<(usize, usize, Dir)> = Vec::new();
+    queue.push((start_row, start_col, start_dir)); // The problem statement tells us this is the start.
+    beams[start_row][start_col] |= start_dir.mask();
+
+    while let Some(cmd) = queue.pop() {
+        for (r, c, dir) in handle_cmd(in_contraption, optics, &cmd) {
+            if beams[r][c] & dir.mask() == 0 {
+                beams[r][c] |= dir.mask();
+                queue.push((r, c, dir));
+            }
+        }
+    }
+
+    BeamMap { beams }
+}
+
+/// Propagates the initial beam of light defined by the 'start_' parameters throughout 'in_contraption'.
+fn propagate_light(
+    in_contraption: &[Vec<char>],
+    optics: &Optics,
+    start_row: usize,
+    start_col: usize,
+    start_dir: Dir,
+) -> usize {
+    trace_beams(in_contraption, optics, start_row, start_col, start_dir).energy()
+}
+
+/// Returns every beam start along the edges of the contraption, pointing inwards.
+fn edge_starts(n_rows: usize, n_cols: usize) -> Vec<(usize, usize, Dir)> {
+    let vertical = (0..n_cols).flat_map(|idx| [(0, idx, Dir::Down), (n_rows - 1, idx, Dir::Up)]);
+    let horizontal = (0..n_rows).flat_map(|idx| [(idx, 0, Dir::Right), (idx, n_cols - 1, Dir::Left)]);
+    vertical.chain(horizontal).collect()
+}
+
+/// Solves Part Two by enumerating over all potential ray starting points and returning the max observed energy.
+///
+/// The baseline with one thread runs in under one second on an M1 Pro in Release mode, so it seems we don't need to do
+/// anything fancier to speed up the computations. Rayon took the full input from 218ms down to 31ms. Kept as the
+/// reference for the beam graph below.
+fn get_best_energy(contraption: &[Vec<char>], optics: &Optics) -> usize {
+    edge_starts(contraption.len(), contraption[0].len())
+        .into_par_iter()
+        .map(|(start_row, start_col, start_dir)| propagate_light(contraption, optics, start_row, start_col, start_dir))
+        .max()
+        .expect("A nonzero number of start configurations was expected.")
+}
+
+/// A fixed-size set of tiles, one bit per tile in row-major order.
+#[derive(Clone)]
+struct TileSet(Vec<u64>);
+
+impl TileSet {
+    fn new(n_tiles: usize) -> Self {
+        TileSet(vec![0; n_tiles.div_ceil(64)])
+    }
+
+    fn insert(&mut self, tile: usize) {
+        self.0[tile / 64] |= 1 << (tile % 64);
+    }
+
+    fn union_with(&mut self, other: &TileSet) {
+        for (word, other_word) in self.0.iter_mut().zip(other.0.iter()) {
+            *word |= other_word;
+        }
+    }
+
+    fn contains(&self, tile: usize) -> bool {
+        self.0[tile / 64] & (1 << (tile % 64)) != 0
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+/// A run of beam through tiles which send it on in exactly one direction.
+struct Segment {
+    tiles: TileSet,
+    /// The fork which ends the segment, if the beam does not just leave the contraption, get absorbed or loop.
+    end_fork: Option<usize>,
+}
+
+/// Precomputed beam paths, so that all edge starts can share the work of Part Two.
+///
+/// Forks, i.e., tiles which send a beam on in more than one direction, are the only places where work could be shared
+/// between starts, so we build a graph with one node per fork. Two ways of entering a tile which emit the same
+/// directions (like both flat sides of a splitter) share a node. A node's edges are the segments it emits, each of which
+/// ends in another fork or dies out. Beams can loop, so we condense the graph into strongly connected components and
+/// memoize the union of tiles energized from each component.
+struct BeamGraph {
+    optics: Optics,
+    n_cols: usize,
+    n_tiles: usize,
+    /// Tile index and outgoing directions of every fork, and the reverse lookup.
+    forks: Vec<(usize, u8)>,
+    fork_ids: HashMap<(usize, u8), usize>,
+    /// The component of every fork, and everything energized once a beam reaches that component.
+    component_of: Vec<usize>,
+    component_tiles: Vec<TileSet>,
+}
+
+impl BeamGraph {
+    fn new(map: &[Vec<char>], optics: &Optics) -> Self {
+        let n_rows = map.len();
+        let n_cols = map[0].len();
+        let n_tiles = n_rows * n_cols;
+        let mut forks: Vec<(usize, u8)> = Vec::new();
+        let mut fork_ids: HashMap<(usize, u8), usize> = HashMap::new();
+        for tile in 0..n_tiles {
+            for dir in Dir::ALL {
+                let out_mask = optics.outgoing(map[tile / n_cols][tile % n_cols], dir);
+                if out_mask.count_ones() > 1 && !fork_ids.contains_key(&(tile, out_mask)) {
+                    fork_ids.insert((tile, out_mask), forks.len());
+                    forks.push((tile, out_mask));
+                }
+            }
+        }
+        let mut graph = BeamGraph {
+            optics: optics.clone(),
+            n_cols,
+            n_tiles,
+            forks,
+            fork_ids,
+            component_of: Vec::new(),
+            component_tiles: Vec::new(),
+        };
+
+        // Every fork energizes its own tile plus the segments it emits.
+        let mut own_tiles: Vec<TileSet> = Vec::new();
+        let mut successors: Vec<Vec<usize>> = Vec::new();
+        for (tile, out_mask) in &graph.forks {
+            let (row, col) = (tile / n_cols, tile % n_cols);
+            let mut tiles = TileSet::new(n_tiles);
+            tiles.insert(*tile);
+            let mut next = Vec::new();
+            for dir in Dir::ALL.iter().filter(|dir| out_mask & dir.mask() != 0) {
+                let emitted = match dir {
+                    Dir::Up if row > 0 => (row - 1, col, *dir),
+                    Dir::Down if row + 1 < n_rows => (row + 1, col, *dir),
+                    Dir::Left if col > 0 => (row, col - 1, *dir),
+                    Dir::Right if col + 1 < n_cols => (row, col + 1, *dir),
+                    _ => continue,
+                };
+                let segment = graph.trace_segment(map, emitted);
+                tiles.union_with(&segment.tiles);
+                next.extend(segment.end_fork);
+            }
+            own_tiles.push(tiles);
+            successors.push(next);
+        }
+
+        let components = strongly_connected_components(&successors);
+        graph.component_of = vec![0; graph.forks.len()];
+        for (component_id, members) in components.iter().enumerate() {
+            for node in members {
+                graph.component_of[*node] = component_id;
+            }
+        }
+        // Tarjan's algorithm emits components in reverse topological order, so everything a component can reach is
+        // already computed when we get to it.
+        for members in &components {
+            let mut tiles = TileSet::new(n_tiles);
+            for node in members {
+                tiles.union_with(&own_tiles[*node]);
+                for next in &successors[*node] {
+                    let next_component = graph.component_of[*next];
+                    if next_component < graph.component_tiles.len() {
+                        tiles.union_with(&graph.component_tiles[next_component]);
+                    }
+                }
+            }
+            graph.component_tiles.push(tiles);
+        }
+
+        graph
+    }
+
+    /// Follows a beam from 'start' until it leaves the contraption, gets absorbed, loops, or reaches a fork.
+    fn trace_segment(&self, map: &[Vec<char>], start: (usize, usize, Dir)) -> Segment {
+        let mut tiles = TileSet::new(self.n_tiles);
+        let mut cmd = start;
+        // A beam can only be in 4 * n_tiles distinct states, so a longer path has covered its whole loop.
+        for _ in 0..4 * self.n_tiles {
+            let (row, col, dir) = cmd;
+            let tile = row * self.n_cols + col;
+            tiles.insert(tile);
+            let out_mask = self.optics.outgoing(map[row][col], dir);
+            if out_mask.count_ones() > 1 {
+                return Segment {
+                    tiles,
+                    end_fork: Some(self.fork_ids[&(tile, out_mask)]),
+                };
+            }
+            match handle_cmd(map, &self.optics, &cmd)[..] {
+                [next] if next != start => cmd = next,
+                _ => break,
+            }
+        }
+
+        Segment { tiles, end_fork: None }
+    }
+
+    fn energized(&self, map: &[Vec<char>], start: (usize, usize, Dir)) -> TileSet {
+        let segment = self.trace_segment(map, start);
+        let mut tiles = segment.tiles;
+        if let Some(fork) = segment.end_fork {
+            tiles.union_with(&self.component_tiles[self.component_of[fork]]);
+        }
+        tiles
+    }
+}
+
+/// Returns the strongly connected components of a graph given as adjacency lists, in reverse topological order.
+///
+/// This is an iterative version of Tarjan's algorithm, so deep beam graphs cannot overflow the stack.
+fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n_nodes = successors.len();
+    let mut index = vec![usize::MAX; n_nodes];
+    let mut low_link = vec![0; n_nodes];
+    let mut on_stack = vec![false; n_nodes];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..n_nodes {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        // Each frame is a node and the position of the next successor to visit.
+        let mut call_stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((node, succ_pos)) = call_stack.pop() {
+            if succ_pos == 0 {
+                index[node] = next_index;
+                low_link[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(next) = successors[node].get(succ_pos) {
+                call_stack.push((node, succ_pos + 1));
+                if index[*next] == usize::MAX {
+                    call_stack.push((*next, 0));
+                } else if on_stack[*next] {
+                    low_link[node] = low_link[node].min(index[*next]);
+                }
+                continue;
+            }
+
+            // All successors are done.
+            if low_link[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+            if let Some((parent, _)) = call_stack.last() {
+                low_link[*parent] = low_link[*parent].min(low_link[node]);
+            }
+        }
+    }
+
+    components
+}
+
+/// Solves Part Two with a precomputed beam graph shared by all edge starts, instead of tracing each start from scratch.
+fn get_best_energy_cached(contraption: &[Vec<char>], optics: &Optics) -> usize {
+    let graph = BeamGraph::new(contraption, optics);
+    edge_starts(contraption.len(), contraption[0].len())
+        .into_par_iter()
+        .map(|start| graph.energized(contraption, start).len())
+        .max()
+        .expect("A nonzero number of start configurations was expected.")
+}
+
+/// Counts, for every tile, how many of the Part Two edge starts energize it.
+fn energize_counts(contraption: &[Vec<char>], optics: &Optics) -> Vec<Vec<usize>> {
+    let n_cols = contraption[0].len();
+    let graph = BeamGraph::new(contraption, optics);
+    let mut counts = vec![vec![0; n_cols]; contraption.len()];
+    for start in edge_starts(contraption.len(), n_cols) {
+        let tiles = graph.energized(contraption, start);
+        for (row, count_row) in counts.iter_mut().enumerate() {
+            for (col, count) in count_row.iter_mut().enumerate() {
+                *count += tiles.contains(row * n_cols + col) as usize;
+            }
+        }
+    }
+    counts
+}
+
+/// Writes the energize counts as a PPM image, blending linearly from 'cold' (never energized) to 'hot' (energized by
+/// the most starts).
+fn write_heatmap_ppm(
+    out_fpath: &Path,
+    counts: &[Vec<usize>],
+    cold: (u8, u8, u8),
+    hot: (u8, u8, u8),
+    scale: usize,
+) -> std::io::Result<()> {
+    let max_count = counts.iter().flatten().copied().max().unwrap_or(0).max(1);
+    let lerp = |from: u8, to: u8, t: f64| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+    write_ppm(out_fpath, counts.len(), counts[0].len(), scale, |row, col| {
+        let t = counts[row][col] as f64 / max_count as f64;
+        (lerp(cold.0, hot.0, t), lerp(cold.1, hot.1, t), lerp(cold.2, hot.2, t))
+    })
+}
+
+/// Parses a non-empty, rectangular contraption made only of tiles which 'optics' has rules for.
+fn parse_contraption(in_txt: &str, optics: &Optics) -> anyhow::Result<Vec<Vec<char>>> {
+    let contraption: Vec<Vec<char>> = in_txt.split_terminator('\n').map(|row| row.chars().collect()).collect();
+    anyhow::ensure!(
+        !contraption.is_empty() && !contraption[0].is_empty(),
+        "Expected a non-empty contraption"
+    );
+    for (row_idx, row) in contraption.iter().enumerate() {
+        anyhow::ensure!(
+            row.len() == contraption[0].len(),
+            "Row {} has {} tiles instead of {}",
+            row_idx + 1,
+            row.len(),
+            contraption[0].len()
+        );
+        if let Some(tile) = row.iter().find(|tile| !optics.knows(**tile)) {
+            anyhow::bail!("No optics rule for tile {:?} on row {}", tile, row_idx + 1);
+        }
+    }
+    Ok(contraption)
+}
+
+fn read_contraption(input_fpath: &Path, optics: &Optics) -> anyhow::Result<Vec<Vec<char>>> {
+    let in_txt = crate::input::read_input(input_fpath)?;
+    parse_contraption(&in_txt, optics).with_context(|| format!("Parse contraption from {:?}", input_fpath))
+}
+
+/// A contraption with the standard optics, on which the two Part Two searches can be timed against each other.
+pub struct BestEnergySearch {
+    optics: Optics,
+    contraption: Vec<Vec<char>>,
+}
+
+impl BestEnergySearch {
+    pub fn parse(in_txt: &str) -> anyhow::Result<Self> {
+        let optics = Optics::standard();
+        let contraption = parse_contraption(in_txt, &optics)?;
+        Ok(BestEnergySearch { optics, contraption })
+    }
+
+    /// Traces every edge start from scratch, see 'get_best_energy'.
+    pub fn traced(&self) -> usize {
+        get_best_energy(&self.contraption, &self.optics)
+    }
+
+    /// Shares one beam graph between all edge starts, see 'get_best_energy_cached'.
+    pub fn cached(&self) -> usize {
+        get_best_energy_cached(&self.contraption, &self.optics)
+    }
+}
+
+/// Parses an input without solving it.
+pub fn check_input(in_txt: &str) -> anyhow::Result<()> {
+    parse_contraption(in_txt, &Optics::standard()).map(drop)
+}
+
+pub fn day_16_lava(input_fpath: &Path) -> anyhow::Result<(usize, usize)> {
+    solve_with_optics(input_fpath, &Optics::standard())
+}
+
+/// Solves both parts for a contraption whose tiles behave according to 'optics'.
+fn solve_with_optics(input_fpath: &Path, optics: &Optics) -> anyhow::Result<(usize, usize)> {
+    let contraption = read_contraption(input_fpath, optics)?;
+    // println!("{}", crate::render(&contraption));
+
+    let default_energy = propagate_light(&contraption, optics, 0, 0, Dir::Right);
+    let part_one_answer: usize = default_energy;
+
+    let best_energy = get_best_energy_cached(&contraption, optics);
+    let part_two_answer: usize = best_energy;
+
+    Ok((part_one_answer, part_two_answer))
+}
+
+/// Solves both parts for a contraption given as text, finding the best start with 'best_energy'.
+fn solve_text(in_txt: &str, best_energy: fn(&[Vec<char>], &Optics) -> usize) -> String {
+    let optics = Optics::standard();
+    let answers = parse_contraption(in_txt, &optics).map(|contraption| {
+        let part_one_answer = propagate_light(&contraption, &optics, 0, 0, Dir::Right);
+        (part_one_answer, best_energy(&contraption, &optics))
+    });
+    format!("{:?}", answers.map_err(|e| e.to_string()))
+}
+
+/// Times parsing the contraption, then lighting it from the top left and from the best edge tile.
+pub fn bench() -> DayBench {
+    DayBench {
+        day: 16,
+        input: "input/16.txt",
+        parse: |in_txt| {
+            let optics = Optics::standard();
+            let contraption = parse_contraption(in_txt, &optics)?;
+            Ok(Box::new(Parts {
+                input: (optics, contraption),
+                part_one: |(optics, contraption)| Ok(propagate_light(contraption, optics, 0, 0, Dir::Right)),
+                part_two: Some(|(optics, contraption)| Ok(get_best_energy_cached(contraption, optics))),
+            }))
+        },
+    }
+}
+
+/// Checks the beam graph against tracing every start from scratch on small contraptions.
+pub fn cross_check() -> DayCheck {
+    DayCheck {
+        day: 16,
+        reference: Solver {
+            name: "propagation",
+            solve: |in_txt| solve_text(in_txt, get_best_energy),
+        },
+        optimized: vec![Solver {
+            name: "beam_graph",
+            solve: |in_txt| solve_text(in_txt, get_best_energy_cached),
+        }],
+        random_input: |rng| {
+            let (n_rows, n_cols) = (rng.gen_range(1..=7), rng.gen_range(1..=7));
+            crate::gen::contraption(rng, n_rows, n_cols)
+        },
+    }
+}
+
+pub fn main() -> anyhow::Result<()> {
+    // Pass '--render <input> [out.ppm]' to draw the Part One beams (in the terminal, or as an image), or
+    // '--heatmap <input> <out.ppm>' to draw how many Part Two starts energize each tile. Prefix either with
+    // '--optics <config>' to change how tiles behave, or pass just '--optics <config> <input>' to solve with them.
+    let mut args: Vec<String> = std::env::args().collect();
+    let mut optics = Optics::standard();
+    if args.len() >= 3 && args[1] == "--optics" {
+        optics = Optics::from_file(Path::new(&args[2]))?;
+        args.drain(1..3);
+        if args.len() == 2 {
+            println!("{:?}", solve_with_optics(Path::new(&args[1]), &optics)?);
+            return Ok(());
+        }
+    }
+    if args.len() >= 3 && (args[1] == "--render" || args[1] == "--heatmap") {
+        let contraption = read_contraption(Path::new(&args[2]), &optics)?;
+        let palette = Palette::from_hex("#f0a030", "#7c8ea0", "#1d2021")?;
+        match (args[1].as_str(), args.get(3)) {
+            ("--render", None) => print!(
+                "{}",
+                trace_beams(&contraption, &optics, 0, 0, Dir::Right).render_ansi(&contraption, &palette)
+            ),
+            ("--render", Some(out_fpath)) => trace_beams(&contraption, &optics, 0, 0, Dir::Right).write_ppm(
+                Path::new(out_fpath),
+                &contraption,
+                &palette,
+                8,
+            )?,
+            (_, Some(out_fpath)) => {
+                let counts = energize_counts(&contraption, &optics);
+                write_heatmap_ppm(Path::new(out_fpath), &counts, palette.background, palette.highlight, 8)?
+            }
+            (_, None) => anyhow::bail!("Usage: --heatmap <input> <out.ppm>"),
+        }
+        return Ok(());
+    }
+
+    println!("{:?}", day_16_lava(&PathBuf::from("input/16-demo.txt"))?);
+    println!("{:?}", day_16_lava(&PathBuf::from("input/16.txt"))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_full_problem_16_lava() {
+        assert_eq!((7415, 7943), day_16_lava(&PathBuf::from("input/16.txt")).unwrap());
+    }
+
+    #[test]
+    fn test_beam_graph_matches_propagation() {
+        for input in ["input/16-demo.txt", "input/16.txt"] {
+            let contraption = crate::read_to_char_grid(&PathBuf::from(input));
+            let optics = Optics::standard();
+            let graph = BeamGraph::new(&contraption, &optics);
+            for (row, col, dir) in edge_starts(contraption.len(), contraption[0].len()) {
+                assert_eq!(
+                    propagate_light(&contraption, &optics, row, col, dir),
+                    graph.energized(&contraption, (row, col, dir)).len(),
+                    "Start {row} {col} {dir:?} in {input}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_beam_drawing_16_lava() {
+        let contraption = crate::read_to_char_grid(&PathBuf::from("input/16-demo.txt"));
+        let optics = Optics::standard();
+        let beams = trace_beams(&contraption, &optics, 0, 0, Dir::Right);
+        assert_eq!(46, beams.energy());
+        // Matches the drawing from the puzzle statement.
+        let expected = [
+            ">|<<<\\....",
+            "|v-.\\^....",
+            ".v...|->>>",
+            ".v...v^.|.",
+            ".v...v^...",
+            ".v...v^..\\",
+            ".v../2\\\\..",
+            "<->-/vv|..",
+            ".|<<<2-|.\\",
+            ".v//.|.v..",
+        ];
+        assert_eq!(expected.join("\n"), crate::render(&beams.draw(&contraption)));
+
+        let palette = Palette::from_hex("#f0a030", "#7c8ea0", "#1d2021").unwrap();
+        let ansi = beams.render_ansi(&contraption, &palette);
+        assert_eq!(46, ansi.matches("\x1b[38;2;240;160;48m").count());
+
+        let counts = energize_counts(&contraption, &optics);
+        let n_starts = edge_starts(contraption.len(), contraption[0].len()).len();
+        assert!(counts.iter().flatten().all(|count| *count <= n_starts));
+        // The splitter near the top left corner is energized from 18 of the 40 edge starts.
+        assert_eq!(18, counts[0][1]);
+
+        let out_dir = std::env::temp_dir().join("aoc23-16-render-test");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        beams
+            .write_ppm(&out_dir.join("beams.ppm"), &contraption, &palette, 2)
+            .unwrap();
+        write_heatmap_ppm(
+            &out_dir.join("heat.ppm"),
+            &counts,
+            palette.background,
+            palette.highlight,
+            1,
+        )
+        .unwrap();
+        let header = b"P6\n20 20\n255\n";
+        let image = std::fs::read(out_dir.join("beams.ppm")).unwrap();
+        assert!(image.starts_with(header));
+        assert_eq!(header.len() + 20 * 20 * 3, image.len());
+        assert_eq!(
+            b"P6\n10 10\n255\n".len() + 10 * 10 * 3,
+            std::fs::read(out_dir.join("heat.ppm")).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_custom_optics() {
+        // '#' absorbs everything, 'T' splits any beam three ways (never back), and '>' is a one-way mirror which lets
+        // beams through left-to-right but turns right-to-left beams upwards.
+        let optics = Optics::parse(&format!(
+            "{}\n# * ->\n{}\n{}\n{}\n{}\n> right -> straight\n> left -> up\n> up -> up\n> down -> down\n",
+            STANDARD_OPTICS,
+            "T up -> up left right",
+            "T down -> down left right",
+            "T left -> left up down",
+            "T right -> right up down",
+        ))
+        .unwrap();
+
+        let contraption: Vec<Vec<char>> = ["..#..", ".....", "..T..", ".....", "..>.."]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        // The beam along the top row lights up the absorber, but stops there.
+        assert_eq!(3, propagate_light(&contraption, &optics, 0, 0, Dir::Right));
+        // The splitter lights up its row and column.
+        assert_eq!(9, propagate_light(&contraption, &optics, 2, 0, Dir::Right));
+        // The one-way mirror passes beams going right, but sends beams going left up through the splitter.
+        assert_eq!(5, propagate_light(&contraption, &optics, 4, 0, Dir::Right));
+        assert_eq!(11, propagate_light(&contraption, &optics, 4, 4, Dir::Left));
+
+        // The beam graph has to agree with plain propagation on random contraptions full of custom tiles.
+        let mut rng = StdRng::seed_from_u64(16);
+        let tiles = ['.', '.', '.', '.', '|', '-', '/', '\\', '#', 'T', '>'];
+        for _ in 0..20 {
+            let contraption: Vec<Vec<char>> = (0..12)
+                .map(|_| (0..9).map(|_| tiles[rng.gen_range(0..tiles.len())]).collect())
+                .collect();
+            let graph = BeamGraph::new(&contraption, &optics);
+            for (row, col, dir) in edge_starts(contraption.len(), contraption[0].len()) {
+                assert_eq!(
+                    propagate_light(&contraption, &optics, row, col, dir),
+                    graph.energized(&contraption, (row, col, dir)).len(),
+                    "Start {row} {col} {dir:?} in\n{}",
+                    crate::render(&contraption)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_optics_config_errors() {
+        assert!(Optics::parse("| left up down").is_err());
+        assert!(Optics::parse("| sideways -> up").is_err());
+        assert!(Optics::parse("| left -> north").is_err());
+        assert!(Optics::parse("ab left -> up").is_err());
+        let error = Optics::parse("; ok\n. * -> straight\n. up down -> up").unwrap_err();
+        assert_eq!("Optics rule on line 3", error.to_string());
+
+        // Tiles the config has no rules for are rejected when parsing the contraption.
+        let optics = Optics::parse(". * -> straight\n| up -> up").unwrap();
+        let error = parse_contraption("..\n.-\n", &optics).unwrap_err();
+        assert_eq!("No optics rule for tile '-' on row 2", error.to_string());
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // 0 -> 1 -> 2 -> 0 is a loop feeding into 3 -> 4 -> 3, and 5 stands alone.
+        let successors = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![]];
+        let mut components = strongly_connected_components(&successors);
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(vec![vec![3, 4], vec![0, 1, 2], vec![5]], components);
+    }
+}
@@ -0,0 +1,146 @@
+pub mod day01_trebuchet;
+pub mod day02_cube_conundrum;
+pub mod day03_gear_ratios;
+pub mod day04_scratchcards;
+pub mod day05_seeds;
+pub mod day06_tbd;
+pub mod day07_camel_cards;
+pub mod day08_haunted_wasteland;
+pub mod day09_mirage_maintenance;
+pub mod day10_tbd;
+pub mod day11_cosmic_expansion;
+pub mod day12_hot_springs;
+pub mod day13_point_of_incidence;
+pub mod day14_parabolic;
+pub mod day15_lens_library;
+pub mod day16_lava;
+
+use crate::bench::DayBench;
+use crate::check::DayCheck;
+
+/// Every day which keeps a reference solver next to optimized ones.
+pub fn cross_checks() -> Vec<DayCheck> {
+    vec![
+        day03_gear_ratios::cross_check(),
+        day05_seeds::cross_check(),
+        day12_hot_springs::cross_check(),
+        day16_lava::cross_check(),
+    ]
+}
+
+/// How to time the stages of every day.
+pub fn benches() -> Vec<DayBench> {
+    vec![
+        day01_trebuchet::bench(),
+        day02_cube_conundrum::bench(),
+        day03_gear_ratios::bench(),
+        day04_scratchcards::bench(),
+        day05_seeds::bench(),
+        day06_tbd::bench(),
+        day07_camel_cards::bench(),
+        day08_haunted_wasteland::bench(),
+        day09_mirage_maintenance::bench(),
+        day10_tbd::bench(),
+        day11_cosmic_expansion::bench(),
+        day12_hot_springs::bench(),
+        day13_point_of_incidence::bench(),
+        day14_parabolic::bench(),
+        day15_lens_library::bench(),
+        day16_lava::bench(),
+    ]
+}
+
+/// Parses an input of 'day' without solving it, e.g., to validate it or to fuzz the parser.
+pub fn check_input(day: u32, in_txt: &str) -> anyhow::Result<()> {
+    match day {
+        1 => day01_trebuchet::check_input(in_txt),
+        2 => day02_cube_conundrum::check_input(in_txt),
+        3 => day03_gear_ratios::check_input(in_txt),
+        4 => day04_scratchcards::check_input(in_txt),
+        5 => day05_seeds::check_input(in_txt),
+        6 => day06_tbd::check_input(in_txt),
+        7 => day07_camel_cards::check_input(in_txt),
+        8 => day08_haunted_wasteland::check_input(in_txt),
+        9 => day09_mirage_maintenance::check_input(in_txt),
+        10 => day10_tbd::check_input(in_txt),
+        11 => day11_cosmic_expansion::check_input(in_txt),
+        12 => day12_hot_springs::check_input(in_txt),
+        13 => day13_point_of_incidence::check_input(in_txt),
+        14 => day14_parabolic::check_input(in_txt),
+        15 => day15_lens_library::check_input(in_txt),
+        16 => day16_lava::check_input(in_txt),
+        _ => anyhow::bail!("No parser for day {}", day),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::minimize;
+    use crate::gen::{GenRng, DAYS};
+    use rand::{Rng, SeedableRng};
+
+    /// The demo inputs of a day, which seed its fuzzing corpus too.
+    fn demo_inputs(day: u32) -> Vec<String> {
+        let prefix = format!("{:02}-demo", day);
+        let mut fpaths: Vec<_> = std::fs::read_dir("input")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|fpath| fpath.file_name().unwrap().to_string_lossy().starts_with(&prefix))
+            .collect();
+        fpaths.sort();
+        fpaths
+            .iter()
+            .map(|fpath| std::fs::read_to_string(fpath).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_demo_and_generated_inputs_parse() {
+        for day in DAYS {
+            for in_txt in demo_inputs(day)
+                .into_iter()
+                .chain([crate::gen::generate(day, 10, 0).unwrap()])
+            {
+                if let Err(e) = check_input(day, &in_txt) {
+                    panic!("Day {}: {:?}\n{}", day, e, in_txt);
+                }
+            }
+        }
+    }
+
+    /// A cheap stand-in for the fuzz targets: mangled demo inputs must be rejected with an error, not a panic.
+    #[test]
+    fn test_mangled_inputs_never_panic() {
+        let mut rng = GenRng::seed_from_u64(46);
+        let junk = [
+            '\n', ' ', ':', ',', '=', '-', '|', '#', '.', 'S', '0', '9', '\u{feff}', 'é',
+        ];
+        for day in DAYS {
+            for in_txt in demo_inputs(day) {
+                let chars: Vec<char> = in_txt.chars().collect();
+                for _ in 0..200 {
+                    let mut mangled = chars.clone();
+                    for _ in 0..rng.gen_range(1..=4) {
+                        let idx = rng.gen_range(0..=mangled.len());
+                        match rng.gen_range(0..3) {
+                            0 if idx < mangled.len() => {
+                                mangled.remove(idx);
+                            }
+                            1 => mangled.insert(idx, junk[rng.gen_range(0..junk.len())]),
+                            _ => mangled.truncate(idx),
+                        }
+                    }
+                    let mangled: String = mangled.into_iter().collect();
+                    let outcome = std::panic::catch_unwind(|| check_input(day, &mangled));
+                    if outcome.is_err() {
+                        let minimized = minimize(&mangled, |candidate| {
+                            std::panic::catch_unwind(|| check_input(day, candidate)).is_err()
+                        });
+                        panic!("Day {} parser panicked on {:?}", day, minimized);
+                    }
+                }
+            }
+        }
+    }
+}
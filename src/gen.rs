@@ -0,0 +1,623 @@
+//! Random puzzle inputs for stress-testing the solvers.
+//!
+//! Every generator produces input in the same format as the real puzzle and respects the guarantees the puzzle text
+//! makes, e.g., Day 10 mazes contain a single loop through 'S' and Day 13 patterns have exactly one clean and one
+//! smudged reflection. The generators draw from ChaCha8, whose output is stable across platforms and 'rand' releases,
+//! so a (day, size, seed) triplet always reproduces the same input.
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashSet};
+
+pub type GenRng = rand_chacha::ChaCha8Rng;
+
+/// The days which have a generator.
+pub const DAYS: std::ops::RangeInclusive<u32> = 1..=16;
+
+/// Generates an input for 'day' with the given seed. What 'size' means depends on the day, e.g., the number of lines or
+/// the side of the grid, and is documented on each generator.
+pub fn generate(day: u32, size: usize, seed: u64) -> anyhow::Result<String> {
+    let rng = &mut GenRng::seed_from_u64(seed);
+    let size = size.max(1);
+    Ok(match day {
+        1 => calibration_document(rng, size),
+        2 => cube_games(rng, size),
+        3 => engine_schematic(rng, size, size),
+        4 => scratchcards(rng, size),
+        5 => almanac(rng, size),
+        6 => races(rng, size),
+        7 => camel_hands(rng, size),
+        8 => desert_map(rng, size),
+        9 => oasis_report(rng, size),
+        10 => pipe_maze(rng, size),
+        11 => galaxy_image(rng, size),
+        12 => spring_records(rng, size, 20),
+        13 => mirror_patterns(rng, size),
+        14 => rock_dish(rng, size),
+        15 => init_sequence(rng, size),
+        16 => contraption(rng, size, size),
+        _ => anyhow::bail!("No generator for day {}", day),
+    })
+}
+
+fn lines_to_string(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// Draws a grid whose cells are picked by 'cell'.
+fn random_grid(rng: &mut GenRng, n_rows: usize, n_cols: usize, cell: impl Fn(&mut GenRng) -> char) -> String {
+    lines_to_string((0..n_rows).map(|_| (0..n_cols).map(|_| cell(rng)).collect::<String>()))
+}
+
+/// Day 1: 'size' lines of letters, digits and spelled out digits, each with at least one actual digit.
+pub fn calibration_document(rng: &mut GenRng, size: usize) -> String {
+    const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    lines_to_string((0..size).map(|_| {
+        let mut line = String::new();
+        for _ in 0..rng.gen_range(1..=6) {
+            match rng.gen_range(0..3) {
+                0 => line.push(char::from_digit(rng.gen_range(1..=9), 10).unwrap()),
+                1 => line.push_str(WORDS.choose(rng).unwrap()),
+                _ => line.extend((0..rng.gen_range(1..=4)).map(|_| rng.gen_range(b'a'..=b'z') as char)),
+            }
+        }
+        let digit_at = rng.gen_range(0..=line.len());
+        line.insert(digit_at, char::from_digit(rng.gen_range(1..=9), 10).unwrap());
+        line
+    }))
+}
+
+/// Day 2: 'size' games of up to six rounds each.
+pub fn cube_games(rng: &mut GenRng, size: usize) -> String {
+    lines_to_string((1..=size).map(|id| {
+        let rounds: Vec<String> = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let n_colors = rng.gen_range(1..=3);
+                let colors: Vec<String> = ["red", "green", "blue"]
+                    .choose_multiple(rng, n_colors)
+                    .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                    .collect();
+                colors.join(", ")
+            })
+            .collect();
+        format!("Game {}: {}", id, rounds.join("; "))
+    }))
+}
+
+/// Day 3: an 'n_rows' by 'n_cols' schematic of numbers with up to three digits and symbols.
+pub fn engine_schematic(rng: &mut GenRng, n_rows: usize, n_cols: usize) -> String {
+    const SYMBOLS: &[u8] = b"*#+$/@=%-&";
+    lines_to_string((0..n_rows).map(|_| {
+        let mut row = String::new();
+        while row.len() < n_cols {
+            match rng.gen_range(0..10) {
+                0..=5 => row.push('.'),
+                6..=8 => {
+                    let value = rng.gen_range(1..1000).to_string();
+                    row += &value[..value.len().min(n_cols - row.len())];
+                }
+                _ => row.push(*SYMBOLS.choose(rng).unwrap() as char),
+            }
+        }
+        row
+    }))
+}
+
+/// Day 4: 'size' cards with ten winning and 25 owned numbers. No card wins copies of cards past the end of the table.
+pub fn scratchcards(rng: &mut GenRng, size: usize) -> String {
+    let numbers: Vec<u32> = (1..100).collect();
+    lines_to_string((1..=size).map(|id| {
+        let winning: Vec<u32> = numbers.choose_multiple(rng, 10).copied().collect();
+        let n_wins = rng.gen_range(0..=10.min(size - id));
+        let losing: Vec<u32> = numbers.iter().filter(|nr| !winning.contains(nr)).copied().collect();
+        let mut owned: Vec<u32> = winning[..n_wins].to_vec();
+        owned.extend(losing.choose_multiple(rng, 25 - n_wins));
+        owned.shuffle(rng);
+
+        let fmt = |nrs: &[u32]| nrs.iter().map(|nr| format!("{:>2}", nr)).collect::<Vec<_>>().join(" ");
+        format!("Card {:>3}: {} | {}", id, fmt(&winning), fmt(&owned))
+    }))
+}
+
+/// Day 5: an almanac with 'size' seed ranges and up to 'size + 1' rules per map, over ids below '100 * size'. The
+/// rules of a map cover disjoint source ranges.
+pub fn almanac(rng: &mut GenRng, size: usize) -> String {
+    let max_id = 100 * size;
+    let seeds: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.gen_range(0..max_id), rng.gen_range(1..=10 * size)))
+        .collect();
+    let mut in_txt = format!("seeds: {}\n", seeds.join(" "));
+    for name in [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ] {
+        let mut cuts: Vec<usize> = (0..2 * rng.gen_range(0..=size + 1))
+            .map(|_| rng.gen_range(0..max_id))
+            .collect();
+        cuts.sort();
+        let mut rules: Vec<String> = cuts
+            .chunks(2)
+            .filter(|cut| cut[0] < cut[1])
+            .map(|cut| format!("{} {} {}", rng.gen_range(0..max_id), cut[0], cut[1] - cut[0]))
+            .collect();
+        rules.shuffle(rng);
+        in_txt += &format!("\n{} map:\n", name);
+        in_txt += &lines_to_string(rules);
+    }
+    in_txt
+}
+
+/// Day 6: 'size' races, at most four so that the race Part Two glues them into stays small enough to brute force.
+/// Every record can be beaten.
+pub fn races(rng: &mut GenRng, size: usize) -> String {
+    let times: Vec<usize> = (0..size.min(4)).map(|_| rng.gen_range(7..100)).collect();
+    let records: Vec<usize> = times.iter().map(|t| rng.gen_range(0..(t / 2) * (t - t / 2))).collect();
+    let fmt = |nrs: &[usize]| nrs.iter().map(|nr| format!(" {:>4}", nr)).collect::<String>();
+    format!("Time:    {}\nDistance:{}\n", fmt(&times), fmt(&records))
+}
+
+/// Day 7: 'size' distinct hands with their bids.
+pub fn camel_hands(rng: &mut GenRng, size: usize) -> String {
+    const CARDS: &[u8] = b"23456789TJQKA";
+    let mut seen = HashSet::new();
+    let mut lines = Vec::with_capacity(size);
+    while lines.len() < size {
+        // Drawing from a few card kinds at a time makes the stronger hands show up too.
+        let n_kinds = rng.gen_range(1..=5);
+        let kinds: Vec<u8> = CARDS.choose_multiple(rng, n_kinds).copied().collect();
+        let hand: String = (0..5).map(|_| *kinds.choose(rng).unwrap() as char).collect();
+        if seen.insert(hand.clone()) {
+            lines.push(format!("{} {}", hand, rng.gen_range(1..=1000)));
+        }
+    }
+    lines_to_string(lines)
+}
+
+/// Day 8: a network with 'size' ghosts (at most six), starting from 'AAA' and other nodes ending in 'A'.
+///
+/// Like in the real puzzle, every ghost walks a cycle: it reaches its only 'Z' node after 'n_steps * p' steps for a
+/// distinct prime 'p', and the 'Z' node leads back to where the start led. Turning the wrong way leads to a random node.
+pub fn desert_map(rng: &mut GenRng, size: usize) -> String {
+    const PRIMES: [usize; 6] = [2, 3, 5, 7, 11, 13];
+    let n_steps = rng.gen_range(2..=(size + 2).min(300));
+    let instructions: Vec<usize> = (0..n_steps).map(|_| rng.gen_range(0..2)).collect();
+
+    let mut names: HashSet<String> = ["AAA", "ZZZ"].iter().map(|name| name.to_string()).collect();
+    let mut random_name = |rng: &mut GenRng, last: Option<u8>| loop {
+        let mut name: Vec<u8> = (0..2).map(|_| rng.gen_range(b'A'..=b'Z')).collect();
+        name.push(last.unwrap_or_else(|| rng.gen_range(b'B'..=b'Y')));
+        let name = String::from_utf8(name).unwrap();
+        if names.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    // Each ghost's path, where the node at index 'step' must turn towards the next one.
+    let mut paths: Vec<Vec<String>> = Vec::new();
+    for (ghost_idx, prime) in PRIMES.iter().take(size.min(PRIMES.len())).enumerate() {
+        let (start, end) = if ghost_idx == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (random_name(rng, Some(b'A')), random_name(rng, Some(b'Z')))
+        };
+        let mut path = vec![start];
+        path.extend((1..n_steps * prime).map(|_| random_name(rng, None)));
+        path.push(end);
+        paths.push(path);
+    }
+
+    let all_nodes: Vec<String> = paths.iter().flatten().cloned().collect();
+    let mut lines = Vec::new();
+    for path in &paths {
+        for (step, node) in path.iter().enumerate() {
+            // The 'Z' node comes at a multiple of 'n_steps', so it turns like the start, towards the second node.
+            let next = &path[if step + 1 == path.len() { 1 } else { step + 1 }];
+            let decoy = all_nodes.choose(rng).unwrap();
+            let (left, right) = match instructions[step % n_steps] {
+                0 => (next, decoy),
+                _ => (decoy, next),
+            };
+            lines.push(format!("{} = ({}, {})", node, left, right));
+        }
+    }
+    lines.shuffle(rng);
+
+    let instructions: String = instructions.iter().map(|dir| ['L', 'R'][*dir]).collect();
+    format!("{}\n\n{}", instructions, lines_to_string(lines))
+}
+
+/// Day 9: 'size' sequences of 21 values of polynomials of degree at most four.
+pub fn oasis_report(rng: &mut GenRng, size: usize) -> String {
+    lines_to_string((0..size).map(|_| {
+        let coeffs: Vec<i64> = (0..=rng.gen_range(0..=4)).map(|_| rng.gen_range(-5..=5)).collect();
+        let values: Vec<String> = (0..21i64)
+            .map(|x| coeffs.iter().rev().fold(0, |acc, c| acc * x + c).to_string())
+            .collect();
+        values.join(" ")
+    }))
+}
+
+/// Day 10: a maze with a single loop through 'S', which winds around a random tree on a 'size' by 'size' lattice. The
+/// maze is '2 * size + 2' tiles wide and filled with junk pipes, none of which connect to 'S'.
+pub fn pipe_maze(rng: &mut GenRng, size: usize) -> String {
+    // Grow a random tree on the lattice. Node (i, j) is cell (2i, 2j) of a fine grid, and the edge to a neighbor is
+    // the cell between them. The thickened tree has no holes, so its outline is a single loop.
+    let n_fine = 2 * size - 1;
+    let mut region = vec![vec![false; n_fine]; n_fine];
+    let root = (rng.gen_range(0..size), rng.gen_range(0..size));
+    region[2 * root.0][2 * root.1] = true;
+    let mut frontier = vec![root];
+    let mut n_nodes_left = rng.gen_range(0..size * size);
+    while !frontier.is_empty() && n_nodes_left > 0 {
+        let idx = rng.gen_range(0..frontier.len());
+        let (i, j) = frontier[idx];
+        let unvisited: Vec<(usize, usize)> = [(0, 1), (2, 1), (1, 0), (1, 2)]
+            .iter()
+            .filter_map(|(di, dj)| Some(((i + di).checked_sub(1)?, (j + dj).checked_sub(1)?)))
+            .filter(|(ni, nj)| *ni < size && *nj < size && !region[2 * ni][2 * nj])
+            .collect();
+        match unvisited.choose(rng) {
+            None => {
+                frontier.swap_remove(idx);
+            }
+            Some(&(ni, nj)) => {
+                region[2 * ni][2 * nj] = true;
+                region[i + ni][j + nj] = true;
+                frontier.push((ni, nj));
+                n_nodes_left -= 1;
+            }
+        }
+    }
+
+    // Collect the outline as edges between cell corners, then walk it.
+    let in_region = |r: isize, c: isize| {
+        r >= 0 && c >= 0 && (r as usize) < n_fine && (c as usize) < n_fine && region[r as usize][c as usize]
+    };
+    let mut outline: BTreeMap<(usize, usize), Vec<(usize, usize)>> = BTreeMap::new();
+    let mut add_edge = |a: (usize, usize), b: (usize, usize)| {
+        outline.entry(a).or_default().push(b);
+        outline.entry(b).or_default().push(a);
+    };
+    for (r, row) in region.iter().enumerate() {
+        for (c, _) in row.iter().enumerate().filter(|(_, inside)| **inside) {
+            let (ri, ci) = (r as isize, c as isize);
+            if !in_region(ri - 1, ci) {
+                add_edge((r, c), (r, c + 1));
+            }
+            if !in_region(ri + 1, ci) {
+                add_edge((r + 1, c), (r + 1, c + 1));
+            }
+            if !in_region(ri, ci - 1) {
+                add_edge((r, c), (r + 1, c));
+            }
+            if !in_region(ri, ci + 1) {
+                add_edge((r, c + 1), (r + 1, c + 1));
+            }
+        }
+    }
+    let first = *outline.keys().next().unwrap();
+    let mut tour = vec![first];
+    let mut prev = first;
+    let mut cur = outline[&first][0];
+    while cur != first {
+        tour.push(cur);
+        let next = *outline[&cur].iter().find(|n| **n != prev).unwrap();
+        (prev, cur) = (cur, next);
+    }
+
+    // Corner (y, x) becomes tile (y + 1, x + 1), leaving a border of junk around the loop.
+    let n_tiles = n_fine + 3;
+    let mut maze: Vec<Vec<char>> = (0..n_tiles)
+        .map(|_| {
+            (0..n_tiles)
+                .map(|_| *b".|-LJ7F..".choose(rng).unwrap() as char)
+                .collect()
+        })
+        .collect();
+    for (idx, &(y, x)) in tour.iter().enumerate() {
+        let neighbors = [tour[(idx + tour.len() - 1) % tour.len()], tour[(idx + 1) % tour.len()]];
+        let up = neighbors.contains(&(y.wrapping_sub(1), x));
+        let down = neighbors.contains(&(y + 1, x));
+        let left = neighbors.contains(&(y, x.wrapping_sub(1)));
+        maze[y + 1][x + 1] = match (up, down, left) {
+            (true, true, _) => '|',
+            (true, false, true) => 'J',
+            (true, false, false) => 'L',
+            (false, true, true) => '7',
+            (false, true, false) => 'F',
+            (false, false, _) => '-',
+        };
+    }
+
+    let s_idx = rng.gen_range(0..tour.len());
+    let (sy, sx) = (tour[s_idx].0 + 1, tour[s_idx].1 + 1);
+    maze[sy][sx] = 'S';
+    // Only the two loop tiles next to 'S' may connect to it.
+    let s_links: Vec<(usize, usize)> = [
+        tour[(s_idx + tour.len() - 1) % tour.len()],
+        tour[(s_idx + 1) % tour.len()],
+    ]
+    .iter()
+    .map(|(y, x)| (y + 1, x + 1))
+    .collect();
+    for (ny, nx, towards_s) in [
+        (sy - 1, sx, "|7F"),
+        (sy + 1, sx, "|LJ"),
+        (sy, sx - 1, "-LF"),
+        (sy, sx + 1, "-J7"),
+    ] {
+        if !s_links.contains(&(ny, nx)) && towards_s.contains(maze[ny][nx]) {
+            maze[ny][nx] = '.';
+        }
+    }
+
+    lines_to_string(maze.into_iter().map(|row| row.into_iter().collect()))
+}
+
+/// Day 11: a 'size' by 'size' image with a few galaxies, so some rows and columns are empty.
+pub fn galaxy_image(rng: &mut GenRng, size: usize) -> String {
+    let mut image: Vec<Vec<char>> = (0..size)
+        .map(|_| (0..size).map(|_| if rng.gen_bool(0.08) { '#' } else { '.' }).collect())
+        .collect();
+    // Make sure there is a pair of galaxies to measure.
+    for _ in 0..2 {
+        let (row, col) = (rng.gen_range(0..size), rng.gen_range(0..size));
+        image[row][col] = '#';
+    }
+    lines_to_string(image.into_iter().map(|row| row.into_iter().collect()))
+}
+
+/// Day 12: 'size' records of up to 'max_len' springs. The cluster sizes come from filling in the unknown springs at
+/// random, so every record has at least one arrangement.
+pub fn spring_records(rng: &mut GenRng, size: usize, max_len: usize) -> String {
+    let mut lines = Vec::with_capacity(size);
+    while lines.len() < size {
+        let statuses: String = (0..rng.gen_range(1..=max_len))
+            .map(|_| *['#', '.', '?'].choose(rng).unwrap())
+            .collect();
+        let filled: String = statuses
+            .chars()
+            .map(|c| if c == '?' { *['#', '.'].choose(rng).unwrap() } else { c })
+            .collect();
+        let sizes: Vec<String> = filled
+            .split('.')
+            .filter(|cluster| !cluster.is_empty())
+            .map(|cluster| cluster.len().to_string())
+            .collect();
+        if !sizes.is_empty() {
+            lines.push(format!("{} {}", statuses, sizes.join(",")));
+        }
+    }
+    lines_to_string(lines)
+}
+
+/// Counts the differing cell pairs when mirroring 'rows' between row 'offset - 1' and row 'offset'.
+fn mirror_mismatches(rows: &[Vec<char>], offset: usize) -> usize {
+    (0..offset)
+        .rev()
+        .zip(offset..rows.len())
+        .map(|(above, below)| rows[above].iter().zip(&rows[below]).filter(|(a, b)| a != b).count())
+        .sum()
+}
+
+fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    (0..grid[0].len())
+        .map(|col| grid.iter().map(|row| row[col]).collect())
+        .collect()
+}
+
+/// Returns (clean, smudged), the number of mirror lines with no differences and with exactly one, over both axes.
+fn count_reflections(pattern: &[Vec<char>]) -> (usize, usize) {
+    let cols = transpose(pattern);
+    let mismatches = (1..pattern.len())
+        .map(|offset| mirror_mismatches(pattern, offset))
+        .chain((1..cols.len()).map(|offset| mirror_mismatches(&cols, offset)));
+    mismatches.fold((0, 0), |(clean, smudged), n| {
+        (clean + (n == 0) as usize, smudged + (n == 1) as usize)
+    })
+}
+
+/// Draws a pattern with exactly one clean reflection and exactly one reflection with a single smudge.
+///
+/// The pattern is symmetric along a vertical line for Part One and a horizontal one for Part Two, then one cell is
+/// flipped which the horizontal mirror sees but the vertical one does not. The axes are swapped half of the time.
+fn mirror_pattern(rng: &mut GenRng) -> Vec<Vec<char>> {
+    // Folds 'idx' onto the near side of a mirror line at 'offset', returning whether the line sees it.
+    let fold = |idx: usize, offset: usize, len: usize| {
+        let width = offset.min(len - offset);
+        let seen = idx + width >= offset && idx < offset + width;
+        (
+            if seen && idx >= offset {
+                2 * offset - 1 - idx
+            } else {
+                idx
+            },
+            seen,
+        )
+    };
+
+    loop {
+        let (n_rows, n_cols) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+        let (col_offset, row_offset) = (rng.gen_range(1..n_cols), rng.gen_range(1..n_rows));
+        let base: Vec<Vec<char>> = (0..n_rows)
+            .map(|_| (0..n_cols).map(|_| *['#', '.'].choose(rng).unwrap()).collect())
+            .collect();
+        let mut pattern: Vec<Vec<char>> = (0..n_rows)
+            .map(|r| {
+                (0..n_cols)
+                    .map(|c| base[fold(r, row_offset, n_rows).0][fold(c, col_offset, n_cols).0])
+                    .collect()
+            })
+            .collect();
+
+        let rows_seen: Vec<usize> = (0..n_rows).filter(|r| fold(*r, row_offset, n_rows).1).collect();
+        let cols_unseen: Vec<usize> = (0..n_cols).filter(|c| !fold(*c, col_offset, n_cols).1).collect();
+        let (Some(&row), Some(&col)) = (rows_seen.choose(rng), cols_unseen.choose(rng)) else {
+            continue;
+        };
+        pattern[row][col] = if pattern[row][col] == '#' { '.' } else { '#' };
+
+        if count_reflections(&pattern) == (1, 1) {
+            return if rng.gen_bool(0.5) {
+                transpose(&pattern)
+            } else {
+                pattern
+            };
+        }
+    }
+}
+
+/// Day 13: 'size' patterns, each with exactly one clean and one smudged reflection.
+pub fn mirror_patterns(rng: &mut GenRng, size: usize) -> String {
+    let patterns: Vec<String> = (0..size)
+        .map(|_| lines_to_string(mirror_pattern(rng).into_iter().map(|row| row.into_iter().collect())))
+        .collect();
+    patterns.join("\n")
+}
+
+/// Day 14: a 'size' by 'size' dish of round and cube-shaped rocks.
+pub fn rock_dish(rng: &mut GenRng, size: usize) -> String {
+    random_grid(rng, size, size, |rng| match rng.gen_range(0..20) {
+        0..=4 => 'O',
+        5..=7 => '#',
+        _ => '.',
+    })
+}
+
+/// Day 15: 'size' steps on a few labels, so that lenses get replaced and removed.
+pub fn init_sequence(rng: &mut GenRng, size: usize) -> String {
+    let labels: Vec<String> = (0..size / 4 + 1)
+        .map(|_| {
+            (0..rng.gen_range(2..=6))
+                .map(|_| rng.gen_range(b'a'..=b'z') as char)
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_bool(0.3) {
+                format!("{}-", label)
+            } else {
+                format!("{}={}", label, rng.gen_range(1..=9))
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+/// Day 16: an 'n_rows' by 'n_cols' contraption of mirrors and splitters.
+pub fn contraption(rng: &mut GenRng, n_rows: usize, n_cols: usize) -> String {
+    random_grid(rng, n_rows, n_cols, |rng| match rng.gen_range(0..12) {
+        0 => '|',
+        1 => '-',
+        2 => '/',
+        3 => '\\',
+        _ => '.',
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators_are_deterministic() {
+        for day in DAYS {
+            let input = generate(day, 6, 42).unwrap();
+            assert!(!input.is_empty());
+            assert_eq!(input, generate(day, 6, 42).unwrap(), "Day {}", day);
+            assert_ne!(input, generate(day, 6, 43).unwrap(), "Day {}", day);
+        }
+        assert!(generate(26, 6, 42).is_err());
+    }
+
+    /// Walks the loop through 'S' with its own pipe logic, checking that it is the only one, and returns its length.
+    fn walk_maze_loop(maze: &str, seed: u64) -> usize {
+        let maze: Vec<Vec<char>> = maze.lines().map(|row| row.chars().collect()).collect();
+        let (sy, sx) = (0..maze.len())
+            .flat_map(|y| (0..maze.len()).map(move |x| (y, x)))
+            .find(|(y, x)| maze[*y][*x] == 'S')
+            .unwrap();
+        let exits = |y: usize, x: usize| -> Vec<(usize, usize)> {
+            let dirs: &[(isize, isize)] = match maze[y][x] {
+                '|' => &[(-1, 0), (1, 0)],
+                '-' => &[(0, -1), (0, 1)],
+                'L' => &[(-1, 0), (0, 1)],
+                'J' => &[(-1, 0), (0, -1)],
+                '7' => &[(1, 0), (0, -1)],
+                'F' => &[(1, 0), (0, 1)],
+                'S' => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+                _ => &[],
+            };
+            dirs.iter()
+                .map(|(dy, dx)| ((y as isize + dy) as usize, (x as isize + dx) as usize))
+                .collect()
+        };
+
+        // Exactly two pipes connect to 'S', and following one leads back to 'S' through the other.
+        let connected: Vec<(usize, usize)> = exits(sy, sx)
+            .into_iter()
+            .filter(|(y, x)| exits(*y, *x).contains(&(sy, sx)))
+            .collect();
+        assert_eq!(2, connected.len(), "Seed {}", seed);
+        let (mut prev, mut cur) = ((sy, sx), connected[0]);
+        let mut loop_len = 1;
+        while cur != (sy, sx) {
+            let next = *exits(cur.0, cur.1).iter().find(|n| **n != prev).unwrap();
+            (prev, cur) = (cur, next);
+            loop_len += 1;
+        }
+        assert_eq!(connected[1], prev);
+        loop_len
+    }
+
+    #[test]
+    fn test_pipe_maze_has_single_loop() {
+        for seed in 0..20 {
+            let loop_len = walk_maze_loop(&generate(10, 1 + seed as usize % 7, seed).unwrap(), seed);
+            assert!(loop_len >= 4 && loop_len.is_multiple_of(2));
+        }
+    }
+
+    /// The junk around the loop must not trip up the actual solver, whose farthest tile is half way around the loop.
+    #[test]
+    fn test_pipe_mazes_are_solved() {
+        for seed in 0..50 {
+            let size = 1 + seed as usize % 7;
+            let maze = generate(10, size, seed).unwrap();
+            let (part_one, _) = crate::days::day10_tbd::solve(&maze)
+                .unwrap_or_else(|e| panic!("Seed {}, size {}: {:?}\n{}", seed, size, e, maze));
+            assert_eq!(
+                walk_maze_loop(&maze, seed) / 2,
+                part_one as usize,
+                "Seed {}, size {}",
+                seed,
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn test_mirror_patterns_have_one_reflection_per_part() {
+        let mut rng = GenRng::seed_from_u64(13);
+        for _ in 0..50 {
+            assert_eq!((1, 1), count_reflections(&mirror_pattern(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn test_scratchcards_stay_in_table() {
+        let cards = generate(4, 30, 4).unwrap();
+        for (idx, line) in cards.lines().enumerate() {
+            let (winning, owned) = line.split_once(':').unwrap().1.split_once('|').unwrap();
+            let winning: HashSet<&str> = winning.split_whitespace().collect();
+            let n_wins = owned.split_whitespace().filter(|nr| winning.contains(nr)).count();
+            assert!(idx + n_wins < 30, "{}", line);
+        }
+    }
+}
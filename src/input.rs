@@ -0,0 +1,225 @@
+// Helpers for reading puzzle inputs from files or stdin.
+//
+// All inputs go through the same normalization, so that files saved on Windows or by editors which leave trailing
+// whitespace behind parse the same as the originals: a leading UTF-8 BOM is dropped, CRLF line endings become LF, and
+// trailing whitespace is stripped from every line (with a warning, since it may hint at a malformed input).
+
+use anyhow::Context;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Opens an input for buffered reading, where "-" means stdin.
+pub fn open_input(input_fpath: &Path) -> anyhow::Result<Box<dyn BufRead>> {
+    if input_fpath == Path::new("-") {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+    let file = File::open(input_fpath).with_context(|| format!("Read input from {:?}", input_fpath))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Reads a whole input, where "-" means stdin, and normalizes it.
+pub fn read_input(input_fpath: &Path) -> anyhow::Result<String> {
+    let mut raw = String::new();
+    open_input(input_fpath)?
+        .read_to_string(&mut raw)
+        .with_context(|| format!("Read input from {:?}", input_fpath))?;
+    let mut stripped = StrippedLines::default();
+    let text = raw
+        .split('\n')
+        .enumerate()
+        .map(|(line_idx, line)| stripped.normalize(line_idx, line))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    stripped.warn(&format!("{:?}", input_fpath));
+    Ok(text)
+}
+
+/// Calls 'f' on every normalized line of 'reader' (without the line terminator), reusing a single buffer so that
+/// arbitrarily large inputs are processed in constant memory.
+pub fn for_each_line<R: BufRead>(reader: R, mut f: impl FnMut(&str)) -> anyhow::Result<()> {
+    try_for_each_line(reader, |line| {
+        f(line);
+        Ok(())
+    })
+}
+
+/// Like 'for_each_line', but stops at the first line for which 'f' fails, and reports that line's number.
+pub fn try_for_each_line<R: BufRead>(
+    mut reader: R,
+    mut f: impl FnMut(&str) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut stripped = StrippedLines::default();
+    let mut line = String::new();
+    for line_idx in 0.. {
+        line.clear();
+        if reader.read_line(&mut line).context("Read input line")? == 0 {
+            break;
+        }
+        f(stripped.normalize(line_idx, line.strip_suffix('\n').unwrap_or(&line)))
+            .with_context(|| format!("Line {}", line_idx + 1))?;
+    }
+    stripped.warn("the input");
+    Ok(())
+}
+
+/// Normalizes single lines, and keeps track of the ones which had trailing whitespace.
+#[derive(Default)]
+struct StrippedLines {
+    n_lines: usize,
+    first_line_idx: Option<usize>,
+}
+
+impl StrippedLines {
+    fn normalize<'a>(&mut self, line_idx: usize, line: &'a str) -> &'a str {
+        let line = if line_idx == 0 {
+            line.strip_prefix('\u{feff}').unwrap_or(line)
+        } else {
+            line
+        };
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            self.n_lines += 1;
+            self.first_line_idx.get_or_insert(line_idx);
+        }
+        trimmed
+    }
+
+    fn warn(&self, source: &str) {
+        if let Some(line_idx) = self.first_line_idx {
+            eprintln!(
+                "Warning: ignored trailing whitespace on {} line(s) of {}, starting with line {}",
+                self.n_lines,
+                source,
+                line_idx + 1
+            );
+        }
+    }
+}
+
+/// Reports running totals to stderr every 'every' lines while streaming, if enabled.
+pub struct Progress {
+    every: Option<usize>,
+    n_lines: usize,
+}
+
+impl Progress {
+    pub fn new(every: Option<usize>) -> Self {
+        Progress { every, n_lines: 0 }
+    }
+
+    /// Counts one more line, and reports the partial result from 'partial' if it is time to.
+    pub fn tick<T: Debug>(&mut self, partial: impl FnOnce() -> T) {
+        self.n_lines += 1;
+        if let Some(every) = self.every {
+            if self.n_lines.is_multiple_of(every) {
+                eprintln!("{} lines: {:?}", self.n_lines, partial());
+            }
+        }
+    }
+}
+
+/// Command line options of the streaming solvers: '--stream <path|-> [--progress <n_lines>]'.
+pub struct StreamArgs {
+    pub input: PathBuf,
+    pub progress_every: Option<usize>,
+}
+
+impl StreamArgs {
+    /// Returns None if the arguments do not start with '--stream'.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Option<Self>> {
+        match args.get(1).map(|s| s.as_str()) {
+            Some("--stream") => {}
+            _ => return Ok(None),
+        }
+        let input = args.get(2).context("Usage: --stream <path|-> [--progress <n_lines>]")?;
+        let progress_every = match (args.get(3).map(|s| s.as_str()), args.get(4)) {
+            (None, _) => None,
+            (Some("--progress"), Some(n_lines)) => {
+                let n_lines: usize = n_lines
+                    .parse()
+                    .with_context(|| format!("Parse progress interval {n_lines}"))?;
+                anyhow::ensure!(n_lines > 0, "The progress interval must be positive");
+                Some(n_lines)
+            }
+            (Some(other), _) => anyhow::bail!("Unexpected argument {:?}", other),
+        };
+        Ok(Some(StreamArgs {
+            input: PathBuf::from(input),
+            progress_every,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_for_each_line() -> anyhow::Result<()> {
+        let mut lines: Vec<String> = Vec::new();
+        for_each_line("a b\n\nc\nd".as_bytes(), |line| lines.push(line.to_string()))?;
+        assert_eq!(vec!["a b", "", "c", "d"], lines);
+
+        let mut n_lines = 0;
+        for_each_line("x\ny\n".as_bytes(), |_| n_lines += 1)?;
+        assert_eq!(2, n_lines);
+        assert!(open_input(Path::new("input/does-not-exist.txt")).is_err());
+
+        let mut n_lines = 0;
+        let err = try_for_each_line("1\n2\nx\n4\n".as_bytes(), |line| {
+            n_lines += 1;
+            line.parse::<u32>().map(drop).context("Parse number")
+        })
+        .unwrap_err();
+        assert_eq!((3, "Line 3".to_string()), (n_lines, err.to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalization() -> anyhow::Result<()> {
+        let messy = "\u{feff}32T3K 765\r\nT55J5 684 \r\n\r\nKK677 28\t\r\n";
+        let mut lines: Vec<String> = Vec::new();
+        for_each_line(messy.as_bytes(), |line| lines.push(line.to_string()))?;
+        assert_eq!(vec!["32T3K 765", "T55J5 684", "", "KK677 28"], lines);
+
+        let mut stripped = StrippedLines::default();
+        assert_eq!("a b", stripped.normalize(0, "\u{feff}a b  "));
+        // Only a BOM at the very start of the input is dropped.
+        assert_eq!("\u{feff}c", stripped.normalize(1, "\u{feff}c\r"));
+        assert_eq!("d", stripped.normalize(2, "d\t"));
+        assert_eq!((2, Some(0)), (stripped.n_lines, stripped.first_line_idx));
+
+        // Whole-file reads keep the line structure, including whether there is a final newline.
+        let original = read_input(Path::new("input/07-demo-00.txt"))?;
+        assert_eq!(std::fs::read_to_string("input/07-demo-00.txt")?, original);
+        let fpath = std::env::temp_dir().join("aoc23-input-normalization-test.txt");
+        std::fs::write(&fpath, format!("\u{feff}{} \r\n", original.replace('\n', "\r\n")))?;
+        assert_eq!(format!("{}\n", original), read_input(&fpath)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_args() -> anyhow::Result<()> {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<String>>();
+        assert!(StreamArgs::from_args(&args("aoc"))?.is_none());
+        assert!(StreamArgs::from_args(&args("aoc input/01.txt"))?.is_none());
+
+        let stream = StreamArgs::from_args(&args("aoc --stream - --progress 1000"))?.unwrap();
+        assert_eq!(PathBuf::from("-"), stream.input);
+        assert_eq!(Some(1000), stream.progress_every);
+        assert_eq!(
+            None,
+            StreamArgs::from_args(&args("aoc --stream x.txt"))?
+                .unwrap()
+                .progress_every
+        );
+
+        assert!(StreamArgs::from_args(&args("aoc --stream")).is_err());
+        assert!(StreamArgs::from_args(&args("aoc --stream - --progress 0")).is_err());
+        assert!(StreamArgs::from_args(&args("aoc --stream - --verbose")).is_err());
+        Ok(())
+    }
+}
@@ -2,7 +2,22 @@
 
 use anyhow::Context;
 use itertools::Itertools;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub mod aho_corasick;
+pub mod bench;
+pub mod check;
+pub mod days;
+pub mod gen;
+pub mod input;
+pub mod ordered_map;
+#[cfg(feature = "torch")]
+pub mod tensor_grid;
+
+pub use aho_corasick::AhoCorasick;
+pub use ordered_map::OrderedMap;
 
 pub fn render(chars: &[Vec<char>]) -> String {
     chars.iter().map(|row| row.iter().collect::<String>()).join("\n")
@@ -35,22 +50,69 @@ pub fn chunk_lines_by_blank(lines: &Vec<String>) -> Vec<Vec<String>> {
 
 /// Reads a file, assumed to hold files of identical lengths, into a vector of vectors.
 pub fn read_to_char_grid(input_fpath: &PathBuf) -> Vec<Vec<char>> {
-    let in_txt = std::fs::read_to_string(input_fpath).unwrap_or_else(|_| panic!("Read input from {:?}", input_fpath));
+    let in_txt =
+        input::read_input(input_fpath).unwrap_or_else(|e| panic!("Read input from {:?}: {:?}", input_fpath, e));
     let rows = in_txt.split_terminator('\n');
     rows.map(|s| s.chars().collect()).collect()
 }
 
+/// Parses an 'RRGGBB' color, optionally prefixed by '#'. Anything after the first six hex digits, such as an alpha
+/// channel, is ignored.
 pub fn parse_color_hex(spec: &str) -> anyhow::Result<(u8, u8, u8)> {
     let safe_chars: &str = spec.trim_start_matches("#");
-    let r = u8::from_str_radix(&safe_chars[0..2], 16).with_context(|| format!("R from {spec}"))?;
-    let g = u8::from_str_radix(&safe_chars[2..4], 16).with_context(|| format!("G from {spec}"))?;
-    let b = u8::from_str_radix(&safe_chars[4..6], 16).with_context(|| format!("B from {spec}"))?;
-    Ok((r, g, b))
+    // Slicing with 'get' rejects short specs and multi-byte characters instead of panicking on a bad index.
+    let channel = |range: std::ops::Range<usize>, name: &str| -> anyhow::Result<u8> {
+        let digits = safe_chars.get(range).with_context(|| format!("{name} from {spec}"))?;
+        u8::from_str_radix(digits, 16).with_context(|| format!("{name} from {spec}"))
+    };
+    Ok((channel(0..2, "R")?, channel(2..4, "G")?, channel(4..6, "B")?))
+}
+
+/// Colors for drawing a grid: one for the tiles of interest, one for the fixed tiles around them, and a background.
+pub struct Palette {
+    pub highlight: (u8, u8, u8),
+    pub fixed: (u8, u8, u8),
+    pub background: (u8, u8, u8),
+}
+
+impl Palette {
+    pub fn from_hex(highlight: &str, fixed: &str, background: &str) -> anyhow::Result<Self> {
+        Ok(Palette {
+            highlight: parse_color_hex(highlight).context("Highlight color")?,
+            fixed: parse_color_hex(fixed).context("Fixed color")?,
+            background: parse_color_hex(background).context("Background color")?,
+        })
+    }
+}
+
+/// Writes a binary PPM (P6) image where each grid tile becomes a 'scale' x 'scale' block of color 'color(row, col)'.
+pub fn write_ppm(
+    out_fpath: &Path,
+    n_rows: usize,
+    n_cols: usize,
+    scale: usize,
+    color: impl Fn(usize, usize) -> (u8, u8, u8),
+) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(out_fpath)?);
+    write!(out, "P6\n{} {}\n255\n", n_cols * scale, n_rows * scale)?;
+    for row in 0..n_rows {
+        let pixel_row: Vec<u8> = (0..n_cols)
+            .flat_map(|col| {
+                let (r, g, b) = color(row, col);
+                [r, g, b].repeat(scale)
+            })
+            .collect();
+        for _ in 0..scale {
+            out.write_all(&pixel_row)?;
+        }
+    }
+    out.flush()
 }
 
 #[cfg(test)]
-mod test {
+mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_color_hex_nominal() -> anyhow::Result<()> {
@@ -59,11 +121,125 @@ mod test {
         assert_eq!((255, 0, 255), parse_color_hex("FF00FF")?);
         assert_eq!((0, 0, 0), parse_color_hex("000000")?);
         assert_eq!((202, 161, 115), parse_color_hex("caa173")?);
+        assert_eq!((255, 255, 255), parse_color_hex("#FFFFFF00")?);
         Ok(())
     }
 
     #[test]
     fn test_parse_color_hex_error() {
         assert!(parse_color_hex("GG1212").is_err());
+        assert!(parse_color_hex("").is_err());
+        assert!(parse_color_hex("#FFF").is_err());
+        assert!(parse_color_hex("é0000").is_err());
+    }
+
+    #[test]
+    fn test_palette_and_ppm() {
+        let palette = Palette::from_hex("#ff0000", "00ff00", "#0000ff").unwrap();
+        assert_eq!((255, 0, 0), palette.highlight);
+        assert!(Palette::from_hex("#ff0000", "#00ff00", "blue").is_err());
+
+        let out_fpath = std::env::temp_dir().join(format!("aoc23-ppm-{}.ppm", std::process::id()));
+        let colors = [palette.highlight, palette.fixed, palette.background];
+        write_ppm(&out_fpath, 1, 3, 2, |_, col| colors[col]).unwrap();
+        let image = std::fs::read(&out_fpath).unwrap();
+        std::fs::remove_file(&out_fpath).unwrap();
+        let header = b"P6\n6 2\n255\n";
+        assert_eq!(header, &image[..header.len()]);
+        let row = [255, 0, 0, 255, 0, 0, 0, 255, 0, 0, 255, 0, 0, 0, 255, 0, 0, 255];
+        assert_eq!([row, row].concat(), &image[header.len()..]);
+    }
+
+    #[test]
+    fn test_chunk_lines_by_blank_edge_cases() {
+        let to_lines = |lines: &[&str]| lines.iter().map(|line| line.to_string()).collect::<Vec<String>>();
+        let empty: Vec<Vec<String>> = vec![vec![]];
+        assert_eq!(empty, chunk_lines_by_blank(&vec![]));
+        assert_eq!(
+            vec![to_lines(&["a"]), vec![]],
+            chunk_lines_by_blank(&to_lines(&["a", ""]))
+        );
+        assert_eq!(
+            vec![to_lines(&["a"]), vec![], to_lines(&["b", "c"])],
+            chunk_lines_by_blank(&to_lines(&["a", "", "", "b", "c"]))
+        );
+    }
+
+    #[test]
+    fn test_render_edge_cases() {
+        assert_eq!("", render(&[]));
+        assert_eq!("", render(&[vec![]]));
+        assert_eq!("#.#", render(&[vec!['#', '.', '#']]));
+        assert_eq!("#\n.", render(&[vec!['#'], vec!['.']]));
+    }
+
+    /// A grid of printable cells, which survive input normalization, with at least one row and one column.
+    fn char_grid() -> impl Strategy<Value = Vec<Vec<char>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(n_rows, n_cols)| {
+            prop::collection::vec(
+                prop::collection::vec(prop::sample::select(vec!['.', '#', 'O', 'S', '7', '|']), n_cols),
+                n_rows,
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_render_lines_are_rows(grid in char_grid()) {
+            let rendered = render(&grid);
+            let rows: Vec<Vec<char>> = rendered.split('\n').map(|row| row.chars().collect()).collect();
+            prop_assert_eq!(grid, rows);
+        }
+
+        #[test]
+        fn prop_read_to_char_grid_parses_rendered_grid(grid in char_grid(), trailing_newline: bool) {
+            let fpath = std::env::temp_dir().join(format!("aoc23-char-grid-{}.txt", std::process::id()));
+            let mut in_txt = render(&grid);
+            if trailing_newline {
+                in_txt.push('\n');
+            }
+            std::fs::write(&fpath, &in_txt).unwrap();
+            let parsed = read_to_char_grid(&fpath);
+            std::fs::remove_file(&fpath).unwrap();
+            prop_assert_eq!(&grid, &parsed);
+            prop_assert_eq!(render(&parsed), render(&grid));
+        }
+
+        #[test]
+        fn prop_chunk_then_join_gives_lines(lines in prop::collection::vec(prop_oneof!["", "[.#]{1,4}"], 0..20)) {
+            let chunks = chunk_lines_by_blank(&lines);
+            prop_assert_eq!(lines.iter().filter(|line| line.is_empty()).count() + 1, chunks.len());
+            prop_assert!(chunks.iter().flatten().all(|line| !line.is_empty()));
+            prop_assert_eq!(lines, chunks.join(&String::new()));
+        }
+
+        #[test]
+        fn prop_chunks_split_on_blank_lines(chunks in prop::collection::vec(prop::collection::vec("[.#]{1,4}", 1..5), 1..6)) {
+            let lines: Vec<String> = chunks.join(&String::new());
+            prop_assert_eq!(chunks, chunk_lines_by_blank(&lines));
+        }
+
+        #[test]
+        fn prop_concat_nums_appends_digits(nums in prop::collection::vec(0..10_000usize, 1..4)) {
+            let expected = nums.iter().fold(0, |acc, &nr| acc * 10usize.pow(nr.to_string().len() as u32) + nr);
+            prop_assert_eq!(expected, concat_nums(&nums));
+        }
+
+        #[test]
+        fn prop_parse_color_hex_round_trips(r: u8, g: u8, b: u8, hash: bool, upper: bool) {
+            let mut spec = format!("{:02x}{:02x}{:02x}", r, g, b);
+            if upper {
+                spec = spec.to_uppercase();
+            }
+            if hash {
+                spec.insert(0, '#');
+            }
+            prop_assert_eq!((r, g, b), parse_color_hex(&spec).unwrap());
+        }
+
+        #[test]
+        fn prop_parse_color_hex_never_panics(spec in "\\PC{0,9}") {
+            let _ = parse_color_hex(&spec);
+        }
     }
 }
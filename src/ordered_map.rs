@@ -0,0 +1,197 @@
+// An insertion-ordered hash map.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// A hash map which iterates in insertion order, with O(1) lookup, update and (amortized) removal.
+///
+/// Entries live in a vector in insertion order and a hash index maps each key to its slot. Removing a key leaves a
+/// tombstone behind, and the vector is compacted once tombstones make up more than half of it. Updating the value of an
+/// existing key keeps its original position.
+#[derive(Clone)]
+pub struct OrderedMap<K, V> {
+    entries: Vec<Option<(K, V)>>,
+    index: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Inserts or updates a value. New keys go to the back, while existing keys keep their position. Returns the old
+    /// value, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(slot) = self.index.get(&key) {
+            let (_, old_value) = self.entries[*slot]
+                .as_mut()
+                .expect("Indexed slots are never tombstones");
+            return Some(std::mem::replace(old_value, value));
+        }
+
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push(Some((key, value)));
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = self.index.get(key)?;
+        self.entries[*slot].as_ref().map(|(_, v)| v)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = self.index.get(key)?;
+        self.entries[*slot].as_mut().map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Removes a key, returning its value if it was present. The relative order of the other entries is unchanged.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = self.index.remove(key)?;
+        let (_, value) = self.entries[slot].take().expect("Indexed slots are never tombstones");
+        if self.entries.len() > 2 * self.index.len() {
+            self.compact();
+        }
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index.clear();
+    }
+
+    /// Drops all tombstones and re-points the index at the new slots.
+    fn compact(&mut self) {
+        self.entries.retain(|entry| entry.is_some());
+        for (slot, (key, _)) in self.entries.iter().flatten().enumerate() {
+            *self.index.get_mut(key).expect("Live entries are always indexed") = slot;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.entries.iter().flatten().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for OrderedMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Two maps are equal if they hold the same entries in the same order.
+impl<K: Hash + Eq + Clone, V: PartialEq> PartialEq for OrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Eq> Eq for OrderedMap<K, V> {}
+
+impl<K: Hash + Eq + Clone, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderedMap::new();
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ordered_map_keeps_insertion_order() {
+        let mut map: OrderedMap<String, i32> = OrderedMap::new();
+        assert!(map.is_empty());
+        assert_eq!(None, map.insert("rn".to_string(), 1));
+        assert_eq!(None, map.insert("cm".to_string(), 2));
+        assert_eq!(None, map.insert("qp".to_string(), 3));
+
+        // Updates keep the original position, and removals close the gap.
+        assert_eq!(Some(1), map.insert("rn".to_string(), 7));
+        assert_eq!(Some(2), map.remove("cm"));
+        assert_eq!(None, map.remove("cm"));
+        assert_eq!(None, map.insert("cm".to_string(), 5));
+
+        assert_eq!(
+            vec![("rn", 7), ("qp", 3), ("cm", 5)],
+            map.iter().map(|(k, v)| (k.as_str(), *v)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&3), map.get("qp"));
+        assert!(!map.contains_key("ab"));
+        assert_eq!(3, map.len());
+    }
+
+    #[test]
+    fn test_ordered_map_compaction_matches_reference() {
+        // Compare against a naive vector-backed map while churning through many removals, to exercise compaction.
+        let mut map: OrderedMap<usize, usize> = OrderedMap::new();
+        let mut reference: Vec<(usize, usize)> = Vec::new();
+        for step in 0..5000usize {
+            let key = (step * 7919) % 97;
+            if step % 3 == 0 {
+                map.remove(&key);
+                reference.retain(|(k, _)| *k != key);
+            } else {
+                map.insert(key, step);
+                match reference.iter_mut().find(|(k, _)| *k == key) {
+                    Some(entry) => entry.1 = step,
+                    None => reference.push((key, step)),
+                }
+            }
+            assert_eq!(reference.len(), map.len());
+        }
+
+        assert_eq!(reference, map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>());
+        assert!(map.entries.len() <= 2 * map.len().max(1));
+    }
+}
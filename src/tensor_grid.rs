@@ -0,0 +1,305 @@
+//! Converts character grids, as read by 'read_to_char_grid', to CPU tensors and back, and offers vectorized versions of
+//! common grid operations.
+//!
+//! A grid of 'rows' by 'cols' cells becomes either an integer tensor of shape (rows, cols) holding each cell's index in
+//! an alphabet, or a one-hot float tensor of shape (symbols, rows, cols). The operations act on the last two dimensions,
+//! so they work on both.
+use anyhow::Context;
+use tch::{Kind, Scalar, Tensor};
+
+/// The direction along which 'flip' mirrors a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    /// Mirrors the grid upside down, i.e., reverses the order of the rows.
+    Rows,
+    /// Mirrors the grid left to right, i.e., reverses the order of the columns.
+    Cols,
+}
+
+impl Axis {
+    fn dim(self) -> i64 {
+        match self {
+            Axis::Rows => -2,
+            Axis::Cols => -1,
+        }
+    }
+}
+
+/// Returns the (rows, cols) shape of a non-empty rectangular grid.
+fn grid_shape(grid: &[Vec<char>]) -> anyhow::Result<(usize, usize)> {
+    let n_cols = grid.first().map_or(0, |row| row.len());
+    anyhow::ensure!(n_cols > 0, "Expected a non-empty grid");
+    for (row_idx, row) in grid.iter().enumerate() {
+        anyhow::ensure!(
+            row.len() == n_cols,
+            "Row {} has {} cells instead of {}",
+            row_idx + 1,
+            row.len(),
+            n_cols
+        );
+    }
+    Ok((grid.len(), n_cols))
+}
+
+/// Returns the position of 'symbol' in 'alphabet', which is its value in index tensors.
+pub fn symbol_index(alphabet: &str, symbol: char) -> anyhow::Result<i64> {
+    alphabet
+        .chars()
+        .position(|other| other == symbol)
+        .map(|idx| idx as i64)
+        .with_context(|| format!("Symbol {:?} is not in the alphabet {:?}", symbol, alphabet))
+}
+
+/// Converts a grid to an i64 tensor of shape (rows, cols) holding the index of every cell in 'alphabet'.
+pub fn to_indices(grid: &[Vec<char>], alphabet: &str) -> anyhow::Result<Tensor> {
+    let (n_rows, n_cols) = grid_shape(grid)?;
+    let indices = grid
+        .iter()
+        .flatten()
+        .map(|cell| symbol_index(alphabet, *cell))
+        .collect::<anyhow::Result<Vec<i64>>>()?;
+    Ok(Tensor::from_slice(&indices).view([n_rows as i64, n_cols as i64]))
+}
+
+/// Converts a tensor of shape (rows, cols) holding indices into 'alphabet' back to a grid.
+pub fn from_indices(indices: &Tensor, alphabet: &str) -> anyhow::Result<Vec<Vec<char>>> {
+    let symbols: Vec<char> = alphabet.chars().collect();
+    let rows = Vec::<Vec<i64>>::try_from(&indices.contiguous())?;
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|idx| {
+                    usize::try_from(*idx)
+                        .ok()
+                        .and_then(|idx| symbols.get(idx).copied())
+                        .with_context(|| format!("Index {} is not in the alphabet {:?}", idx, alphabet))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Converts a grid to an f32 tensor of shape (symbols, rows, cols), where channel 'k' marks the cells holding the 'k'th
+/// symbol of 'alphabet'.
+pub fn to_one_hot(grid: &[Vec<char>], alphabet: &str) -> anyhow::Result<Tensor> {
+    let n_symbols = alphabet.chars().count() as i64;
+    Ok(to_indices(grid, alphabet)?
+        .one_hot(n_symbols)
+        .permute([2, 0, 1])
+        .to_kind(Kind::Float))
+}
+
+/// Converts a one-hot tensor of shape (symbols, rows, cols) back to a grid. Cells with several channels set take the
+/// strongest one, with ties going to the earlier symbol.
+pub fn from_one_hot(one_hot: &Tensor, alphabet: &str) -> anyhow::Result<Vec<Vec<char>>> {
+    from_indices(&one_hot.argmax(0, false), alphabet)
+}
+
+/// Returns a mask of shape (rows, cols) which is 1 where an index tensor holds 'symbol'.
+pub fn mask_of(indices: &Tensor, alphabet: &str, symbol: char) -> anyhow::Result<Tensor> {
+    Ok(indices.eq(symbol_index(alphabet, symbol)?).to_kind(Kind::Int64))
+}
+
+/// Moves every cell 'd_row' rows down and 'd_col' columns right, without wrapping around. Cells which have nothing
+/// moving into them take the value 'fill'.
+pub fn shift(grid: &Tensor, d_row: i64, d_col: i64, fill: impl Into<Scalar>) -> Tensor {
+    let (n_rows, n_cols) = (grid.size()[grid.dim() - 2], grid.size()[grid.dim() - 1]);
+    let shifted = grid.full_like(fill);
+    let (keep_rows, keep_cols) = (n_rows - d_row.abs(), n_cols - d_col.abs());
+    if keep_rows <= 0 || keep_cols <= 0 {
+        return shifted;
+    }
+    let source = grid
+        .narrow(-2, (-d_row).max(0), keep_rows)
+        .narrow(-1, (-d_col).max(0), keep_cols);
+    shifted
+        .narrow(-2, d_row.max(0), keep_rows)
+        .narrow(-1, d_col.max(0), keep_cols)
+        .copy_(&source);
+    shifted
+}
+
+/// Counts, for every cell of a (rows, cols) mask, how many of its neighbors are set, with a single convolution. Only
+/// the four orthogonal neighbors count unless 'diagonals' is set.
+pub fn count_neighbors(mask: &Tensor, diagonals: bool) -> Tensor {
+    let corner = if diagonals { 1.0 } else { 0.0 };
+    let kernel = Tensor::from_slice(&[corner, 1.0, corner, 1.0, 0.0, 1.0, corner, 1.0, corner])
+        .to_kind(Kind::Float)
+        .view([1, 1, 3, 3]);
+    mask.to_kind(Kind::Float)
+        .unsqueeze(0)
+        .unsqueeze(0)
+        .conv2d(&kernel, None::<Tensor>, [1, 1], [1, 1], [1, 1], 1)
+        .squeeze_dim(0)
+        .squeeze_dim(0)
+        .round()
+        .to_kind(Kind::Int64)
+}
+
+/// Swaps the rows and the columns of a grid.
+pub fn transpose(grid: &Tensor) -> Tensor {
+    grid.transpose(-2, -1)
+}
+
+/// Mirrors a grid along 'axis'.
+pub fn flip(grid: &Tensor, axis: Axis) -> Tensor {
+    grid.flip([axis.dim()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::GenRng;
+    use rand::{Rng, SeedableRng};
+    use std::path::PathBuf;
+    use tch::Device;
+
+    const ROCKS: &str = ".#O";
+
+    fn random_grid(rng: &mut GenRng, n_rows: usize, n_cols: usize) -> Vec<Vec<char>> {
+        let symbols: Vec<char> = ROCKS.chars().collect();
+        (0..n_rows)
+            .map(|_| (0..n_cols).map(|_| symbols[rng.gen_range(0..symbols.len())]).collect())
+            .collect()
+    }
+
+    fn transpose_scalar(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+        (0..grid[0].len())
+            .map(|col| grid.iter().map(|row| row[col]).collect())
+            .collect()
+    }
+
+    fn flip_scalar(grid: &[Vec<char>], axis: Axis) -> Vec<Vec<char>> {
+        match axis {
+            Axis::Rows => grid.iter().rev().cloned().collect(),
+            Axis::Cols => grid.iter().map(|row| row.iter().rev().copied().collect()).collect(),
+        }
+    }
+
+    fn shift_scalar(grid: &[Vec<char>], d_row: i64, d_col: i64, fill: char) -> Vec<Vec<char>> {
+        let (n_rows, n_cols) = (grid.len() as i64, grid[0].len() as i64);
+        (0..n_rows)
+            .map(|row| {
+                (0..n_cols)
+                    .map(|col| {
+                        let (src_row, src_col) = (row - d_row, col - d_col);
+                        if (0..n_rows).contains(&src_row) && (0..n_cols).contains(&src_col) {
+                            grid[src_row as usize][src_col as usize]
+                        } else {
+                            fill
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn count_neighbors_scalar(grid: &[Vec<char>], symbol: char, diagonals: bool) -> Vec<Vec<i64>> {
+        let (n_rows, n_cols) = (grid.len() as i64, grid[0].len() as i64);
+        (0..n_rows)
+            .map(|row| {
+                (0..n_cols)
+                    .map(|col| {
+                        let mut count = 0;
+                        for (d_row, d_col) in [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)] {
+                            let (n_row, n_col) = (row + d_row, col + d_col);
+                            let is_diagonal = d_row != 0 && d_col != 0;
+                            if (diagonals || !is_diagonal)
+                                && (0..n_rows).contains(&n_row)
+                                && (0..n_cols).contains(&n_col)
+                                && grid[n_row as usize][n_col as usize] == symbol
+                            {
+                                count += 1;
+                            }
+                        }
+                        count
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let grid = crate::read_to_char_grid(&PathBuf::from("input/14.txt"));
+        let indices = to_indices(&grid, ROCKS).unwrap();
+        assert_eq!(vec![grid.len() as i64, grid[0].len() as i64], indices.size());
+        assert_eq!(grid, from_indices(&indices, ROCKS).unwrap());
+
+        let one_hot = to_one_hot(&grid, ROCKS).unwrap();
+        assert_eq!(vec![3, grid.len() as i64, grid[0].len() as i64], one_hot.size());
+        assert_eq!(grid, from_one_hot(&one_hot, ROCKS).unwrap());
+        // Every cell holds exactly one symbol.
+        let ones = Tensor::ones([grid.len() as i64, grid[0].len() as i64], (Kind::Float, Device::Cpu));
+        assert!(one_hot
+            .sum_dim_intlist([0i64].as_slice(), false, Kind::Float)
+            .equal(&ones));
+    }
+
+    #[test]
+    fn test_conversion_errors() {
+        assert!(to_indices(&[], ROCKS).is_err());
+        assert!(to_indices(&[vec!['.', '#'], vec!['.']], ROCKS).is_err());
+        let err = to_indices(&[vec!['.', 'x']], ROCKS).unwrap_err();
+        assert!(err.to_string().contains("'x'"), "{}", err);
+        assert!(from_indices(&Tensor::from_slice(&[0i64, 3]).view([1, 2]), ROCKS).is_err());
+        assert!(from_indices(&Tensor::from_slice(&[0i64, -1]).view([1, 2]), ROCKS).is_err());
+    }
+
+    #[test]
+    fn test_operations_match_scalar() {
+        let mut rng = GenRng::seed_from_u64(50);
+        for _ in 0..50 {
+            let (n_rows, n_cols) = (rng.gen_range(1..=9), rng.gen_range(1..=9));
+            let grid = random_grid(&mut rng, n_rows, n_cols);
+            let indices = to_indices(&grid, ROCKS).unwrap();
+            let one_hot = to_one_hot(&grid, ROCKS).unwrap();
+
+            assert_eq!(
+                transpose_scalar(&grid),
+                from_indices(&transpose(&indices), ROCKS).unwrap()
+            );
+            assert_eq!(
+                transpose_scalar(&grid),
+                from_one_hot(&transpose(&one_hot), ROCKS).unwrap()
+            );
+            for axis in [Axis::Rows, Axis::Cols] {
+                assert_eq!(
+                    flip_scalar(&grid, axis),
+                    from_indices(&flip(&indices, axis), ROCKS).unwrap()
+                );
+                assert_eq!(
+                    flip_scalar(&grid, axis),
+                    from_one_hot(&flip(&one_hot, axis), ROCKS).unwrap()
+                );
+            }
+
+            let (d_row, d_col) = (rng.gen_range(-10..=10), rng.gen_range(-10..=10));
+            assert_eq!(
+                shift_scalar(&grid, d_row, d_col, '.'),
+                from_indices(&shift(&indices, d_row, d_col, 0), ROCKS).unwrap(),
+                "Shift by ({}, {})",
+                d_row,
+                d_col
+            );
+
+            for diagonals in [false, true] {
+                let mask = mask_of(&indices, ROCKS, 'O').unwrap();
+                assert_eq!(
+                    count_neighbors_scalar(&grid, 'O', diagonals),
+                    Vec::<Vec<i64>>::try_from(&count_neighbors(&mask, diagonals)).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbor_counts_on_full_input() {
+        let grid = crate::read_to_char_grid(&PathBuf::from("input/14.txt"));
+        let mask = mask_of(&to_indices(&grid, ROCKS).unwrap(), ROCKS, '#').unwrap();
+        assert_eq!(
+            count_neighbors_scalar(&grid, '#', true),
+            Vec::<Vec<i64>>::try_from(&count_neighbors(&mask, true)).unwrap()
+        );
+    }
+}